@@ -1,16 +1,20 @@
 /// 分批异步导出功能模块
 ///
-/// 提供大数据量表格的分批处理功能，避免阻塞主线程
-use crate::resource::UrlGuard;
-use crate::validation::{ensure_extension, validate_filename};
-use csv::Writer;
-use std::io::Cursor;
+/// 提供大数据量表格的分批处理功能（CSV、TSV、Excel、ODS、JSON、Markdown 和 HTML），避免阻塞主线程
+use crate::core::{
+    ColumnSelection, ExportFormat, ExportOptions, HtmlTableReader, MergeRange, RowReader,
+    SectionTracker, SpanGrid, TableData, XlsxSheetWriter, apply_options, build_html_bytes,
+    build_json_bytes, build_markdown_bytes, build_ods_bytes, csv_writer, detect_header_rows,
+    download_bytes, find_table, finish_csv, get_document, read_column_alignments, resolve_filename,
+    sanitize_row, save_workbook, select_row_columns,
+};
+use crate::error::{ElementKind, ExportError};
+use crate::i18n::Message;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{
-    Blob, HtmlAnchorElement, HtmlTableCellElement, HtmlTableElement, HtmlTableRowElement,
-    HtmlTableSectionElement, Url,
+    AbortSignal, HtmlCollection, HtmlTableElement, HtmlTableRowElement, HtmlTableSectionElement,
 };
 
 /// 分批异步导出 HTML 表格到 CSV 文件
@@ -28,6 +32,9 @@ use web_sys::{
 /// # 返回值
 /// * `Promise<void>` - 异步操作的 Promise
 ///
+/// # 注意
+/// 需要取消导出时使用 `export_table_batch_with_options` 并设置 `options.signal`
+///
 /// # 示例
 /// ```javascript
 /// import { export_table_to_csv_batch } from './pkg/excel_exporter.js';
@@ -50,126 +57,329 @@ pub async fn export_table_to_csv_batch(
     batch_size: Option<u32>,
     progress_callback: Option<js_sys::Function>,
 ) -> Result<JsValue, JsValue> {
-    // 输入验证
-    if table_id.is_empty() {
-        return Err(JsValue::from_str("表格 ID 不能为空"));
-    }
+    let mut options = ExportOptions {
+        filename,
+        tbody_id,
+        progress_callback,
+        ..ExportOptions::default()
+    };
+    options.set_batch_size(batch_size.unwrap_or(1000))?;
+
+    export_batch(&table_id, &options)
+        .await
+        .map_err(|e| options.js_error(e))?;
+    Ok(JsValue::UNDEFINED)
+}
 
-    let batch_size = batch_size.unwrap_or(1000) as usize;
-    if batch_size == 0 {
-        return Err(JsValue::from_str("批次大小必须大于 0"));
-    }
+/// 使用配置对象分批异步导出 HTML 表格
+///
+/// 与 `export_table_to_csv_batch` 相同，但所有配置通过 `ExportOptions` 传入，
+/// 使用其中的 `tbody_id`、`batch_size`、分隔符、表头处理和列选择设置。
+/// 支持所有导出格式；Excel 和 JSON 格式的进度回调额外接收阶段名称
+/// （`"reading"`、`"writing"`、`"saving"`）作为第二个参数
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `options` - 导出配置
+///
+/// # 返回值
+/// * `Promise<void>` - 异步操作的 Promise；`options.signal` 中止时以 `name` 为 `"AbortError"` 的错误拒绝
+///
+/// # 示例
+/// ```javascript
+/// const options = new ExportOptions();
+/// options.tbody_id = 'my-tbody';
+/// options.batch_size = 500;
+/// options.format = ExportFormat.Xlsx;
+/// options.progress_callback = (progress, stage) => console.log(stage, progress);
+/// await export_table_batch_with_options('my-table', options);
+/// ```
+#[wasm_bindgen]
+pub fn export_table_batch_with_options(
+    table_id: String,
+    options: &ExportOptions,
+) -> js_sys::Promise {
+    // 异步导出函数无法借用参数，复制一份配置供 Future 使用
+    let options = options.clone();
+    wasm_bindgen_futures::future_to_promise(async move {
+        export_batch(&table_id, &options)
+            .await
+            .map_err(|e| options.js_error(e))?;
+        Ok(JsValue::UNDEFINED)
+    })
+}
 
-    // 安全地获取全局的 window 和 document 对象
-    let window = web_sys::window().ok_or_else(|| JsValue::from_str("无法获取 window 对象"))?;
-    let document = window
-        .document()
-        .ok_or_else(|| JsValue::from_str("无法获取 document 对象"))?;
-
-    // 1. 获取主表格（通常包含表头）
-    let table_element = document
-        .get_element_by_id(&table_id)
-        .ok_or_else(|| JsValue::from_str(&format!("找不到 ID 为 '{}' 的表格元素", table_id)))?;
-    let table = table_element
-        .dyn_into::<HtmlTableElement>()
-        .map_err(|_| JsValue::from_str(&format!("元素 '{}' 不是有效的 HTML 表格", table_id)))?;
-    let table_rows = table.rows();
-    let table_row_count = table_rows.length() as usize;
-
-    // 2. 获取数据表格体（如果有）
-    let mut tbody_rows_collection = None;
-    let mut tbody_row_count = 0;
-
-    if let Some(tid) = tbody_id
-        && !tid.is_empty()
-    {
-        let tbody_element = document
-            .get_element_by_id(&tid)
-            .ok_or_else(|| JsValue::from_str(&format!("找不到 ID 为 '{}' 的 tbody 元素", tid)))?;
-
-        // 尝试转换为 HtmlTableSectionElement (tbody)
-        let tbody = tbody_element
-            .dyn_into::<HtmlTableSectionElement>()
-            .map_err(|_| {
-                JsValue::from_str(&format!("元素 '{}' 不是有效的 HTML 表格部分(tbody)", tid))
+/// 分批导出的实现
+async fn export_batch(table_id: &str, options: &ExportOptions) -> Result<(), ExportError> {
+    let format = options.format;
+
+    // 在处理数据前验证文件名（默认为对应语言的 table_export.<扩展名>）
+    let final_filename = resolve_filename(
+        options.filename.clone(),
+        format.extension(),
+        options.resolved_locale(),
+    )?;
+
+    let data = export_table_batch_to_bytes(table_id, options).await?;
+
+    // 创建 Blob 并触发下载
+    download_bytes(&data, &options.mime_type(), &final_filename)
+}
+
+/// 分批读取表格并生成导出文件的字节，不触发下载
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `options` - 导出配置
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - 文件字节
+/// * `Err(ExportError)` - 导出失败或被取消
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub async fn export_table_batch_to_bytes(
+    table_id: &str,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let signal = options.signal.as_ref();
+    check_aborted(signal)?;
+
+    let source = BatchSource::open(table_id, options.tbody_id.as_deref())?;
+
+    let data = match options.format {
+        ExportFormat::Csv | ExportFormat::Tsv => write_csv_batches(&source, options).await?,
+        ExportFormat::Xlsx => write_xlsx_batches(&source, options).await?,
+        ExportFormat::Json | ExportFormat::Ndjson | ExportFormat::Markdown | ExportFormat::Ods => {
+            write_document_batches(&source, options).await?
+        }
+        ExportFormat::Html => write_html_batches(&source, options).await?,
+    };
+
+    // 生成文件期间可能被取消，此时不再创建 Blob 和下载链接
+    check_aborted(signal)?;
+    Ok(data)
+}
+
+/// 分批读取的行来源：主表格的行，后接可选 tbody 的行
+struct BatchSource {
+    table: HtmlTableElement,
+    table_rows: HtmlCollection,
+    table_row_count: usize,
+    tbody_rows: Option<HtmlCollection>,
+    total_rows: usize,
+}
+
+impl BatchSource {
+    /// 查找表格和 tbody 元素
+    fn open(table_id: &str, tbody_id: Option<&str>) -> Result<Self, ExportError> {
+        // 1. 获取主表格（通常包含表头）
+        let table = find_table(table_id)?;
+        let table_rows = table.rows();
+        let table_row_count = table_rows.length() as usize;
+
+        // 2. 获取数据表格体（如果有）
+        let mut tbody_rows = None;
+        let mut tbody_row_count = 0;
+
+        if let Some(tid) = tbody_id
+            && !tid.is_empty()
+        {
+            let tbody_element = get_document()?.get_element_by_id(tid).ok_or_else(|| {
+                ExportError::TbodyNotFound {
+                    tbody_id: tid.to_string(),
+                }
             })?;
 
-        let rows = tbody.rows();
-        tbody_row_count = rows.length() as usize;
-        tbody_rows_collection = Some(rows);
+            // 尝试转换为 HtmlTableSectionElement (tbody)
+            let tbody = tbody_element
+                .dyn_into::<HtmlTableSectionElement>()
+                .map_err(|_| ExportError::InvalidElement {
+                    element_id: tid.to_string(),
+                    expected: ElementKind::Tbody,
+                })?;
+
+            let rows = tbody.rows();
+            tbody_row_count = rows.length() as usize;
+            tbody_rows = Some(rows);
+        }
+
+        let total_rows = table_row_count + tbody_row_count;
+
+        if total_rows == 0 {
+            return Err(ExportError::EmptyTable);
+        }
+
+        Ok(Self {
+            table,
+            table_rows,
+            table_row_count,
+            tbody_rows,
+            total_rows,
+        })
     }
 
-    let total_rows = table_row_count + tbody_row_count;
+    /// 表头行数：优先使用配置，未设置时自动检测（不计隐藏的表头行）
+    fn header_rows(
+        &self,
+        options: &ExportOptions,
+        reader: &RowReader,
+    ) -> Result<usize, ExportError> {
+        if let Some(rows) = options.header_rows {
+            return Ok(rows as usize);
+        }
 
-    if total_rows == 0 {
-        return Err(JsValue::from_str("表格为空，没有数据可导出"));
+        let detected = detect_header_rows(&self.table).min(self.total_rows);
+        let mut visible = 0;
+        for i in 0..detected {
+            visible += usize::from(!reader.is_row_hidden(&self.row(i)?));
+        }
+        Ok(visible)
+    }
+
+    /// 读取开头的 `count` 个可见行并展开合并单元格，用于在流式写入前按表头解析列配置
+    fn leading_rows(
+        &self,
+        count: usize,
+        options: &ExportOptions,
+        reader: &RowReader,
+    ) -> Result<Vec<Vec<String>>, ExportError> {
+        let mut span_grid = SpanGrid::new(options.span_mode);
+        let mut sections = SectionTracker::new();
+        let mut rows = Vec::with_capacity(count);
+        for i in 0..self.total_rows {
+            if rows.len() == count {
+                break;
+            }
+            let row = self.row(i)?;
+            sections.enter(&row, &mut span_grid);
+            let row_data = span_grid.push_row(reader.read_row(&row, i)?);
+            if !reader.is_row_hidden(&row) {
+                rows.push(row_data);
+            }
+        }
+        Ok(rows)
+    }
+
+    /// 读取第 `i` 行（跨越主表格与 tbody）
+    fn row(&self, i: usize) -> Result<HtmlTableRowElement, ExportError> {
+        let row_element = if i < self.table_row_count {
+            // 从主表格读取
+            self.table_rows.get_with_index(i as u32)
+        } else {
+            // 从 tbody 读取
+            self.tbody_rows
+                .as_ref()
+                .and_then(|rows| rows.get_with_index((i - self.table_row_count) as u32))
+        };
+
+        row_element
+            .and_then(|row| row.dyn_into::<HtmlTableRowElement>().ok())
+            .ok_or(ExportError::ReadFailed {
+                row: i,
+                column: None,
+            })
     }
+}
+
+/// 分批读取行并流式写入 CSV
+async fn write_csv_batches(
+    source: &BatchSource,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let total_rows = source.total_rows;
+    let batch_size = options.batch_size as usize;
+    let progress_callback = options.progress_callback.as_ref();
+
+    let reader = RowReader::new(options)?;
+
+    // 需要跳过的表头行数
+    let header_rows = source.header_rows(options, &reader)?;
+    let skip_rows = if options.include_header {
+        0
+    } else {
+        header_rows
+    };
+
+    // 按表头解析列配置，流式写入时逐行应用
+    let leading_rows = source.leading_rows(header_rows.max(1), options, &reader)?;
+    let header_data = &leading_rows[..header_rows.min(leading_rows.len())];
+    let width = leading_rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut selection_width = width;
+    let mut selection = options.column_selection(header_data, selection_width)?;
+
+    let sanitization = options.resolved_sanitization();
 
     // 创建 CSV 写入器
-    let mut wtr = Writer::from_writer(Cursor::new(Vec::new()));
+    let mut wtr = csv_writer(options);
+    let mut span_grid = SpanGrid::new(options.span_mode);
+    let mut sections = SectionTracker::new();
 
     // 报告初始进度
-    if let Some(ref callback) = progress_callback {
+    if let Some(callback) = progress_callback {
         let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(0.0));
     }
 
     // 分批处理数据
     let mut current_row = 0;
+    // 不计隐藏行的行索引
+    let mut visible_row = 0;
     while current_row < total_rows {
         let batch_end = std::cmp::min(current_row + batch_size, total_rows);
 
         // 处理当前批次
         for i in current_row..batch_end {
-            let row_element = if i < table_row_count {
-                // 从主表格读取
-                table_rows.get_with_index(i as u32)
-            } else {
-                // 从 tbody 读取
-                if let Some(ref rows) = tbody_rows_collection {
-                    rows.get_with_index((i - table_row_count) as u32)
-                } else {
-                    None
-                }
-            };
-
-            let row = row_element
-                .ok_or_else(|| JsValue::from_str(&format!("无法获取第 {} 行数据", i + 1)))?;
-
-            let row = row
-                .dyn_into::<HtmlTableRowElement>()
-                .map_err(|_| JsValue::from_str(&format!("第 {} 行不是有效的表格行", i + 1)))?;
-
-            let mut row_data = Vec::new();
-            // 遍历每一行中的每一个单元格
-            let cells = row.cells();
-            let cell_count = cells.length();
-
-            for j in 0..cell_count {
-                let cell = cells.get_with_index(j).ok_or_else(|| {
-                    JsValue::from_str(&format!("无法获取第 {} 行第 {} 列单元格", i + 1, j + 1))
-                })?;
+            // 展开 colspan/rowspan，跨批次的 rowspan 状态保存在 span_grid 中；
+            // 隐藏的行也参与展开，保持其后各行的 rowspan 位置正确
+            let row = source.row(i)?;
+            sections.enter(&row, &mut span_grid);
+            let mut row_data = span_grid.push_row(reader.read_row(&row, i)?);
+
+            if reader.is_row_hidden(&row) {
+                continue;
+            }
+            let row_index = visible_row;
+            visible_row += 1;
+            if row_index < skip_rows {
+                continue;
+            }
 
-                let cell = cell.dyn_into::<HtmlTableCellElement>().map_err(|_| {
-                    JsValue::from_str(&format!(
-                        "第 {} 行第 {} 列不是有效的表格单元格",
-                        i + 1,
-                        j + 1
-                    ))
-                })?;
+            // 与一次性导出一致补齐为矩形；之后出现更宽的行时无法回头补齐之前的行，
+            // 按表头和目前为止最宽的行补齐
+            let row_width = width.max(span_grid.width());
+            row_data.resize(row_width, String::new());
 
-                let cell_text = cell.inner_text();
-                row_data.push(cell_text);
+            // 列选择按表格列数解析（只设置 exclude_columns 时输出其余全部列），
+            // 出现更宽的行时按新的列数重新解析，与一次性导出一样保留新出现的列
+            if row_width > selection_width {
+                selection = options.column_selection(header_data, row_width)?;
+                selection_width = row_width;
             }
 
+            let mut row_data = match selection {
+                Some(ref selection) => select_columns(
+                    &row_data,
+                    selection,
+                    span_grid.last_row_merges(),
+                    row_index + 1 == header_rows,
+                ),
+                None => row_data,
+            };
+            sanitize_row(&mut row_data, row_index - skip_rows, sanitization)?;
+
             // 安全地将行数据写入 CSV
             wtr.write_record(&row_data)
-                .map_err(|e| JsValue::from_str(&format!("写入 CSV 数据失败: {}", e)))?;
+                .map_err(|e| ExportError::CsvWriteFailed {
+                    row: Some(i),
+                    message: e.to_string(),
+                })?;
         }
 
         current_row = batch_end;
 
         // 报告进度
-        if let Some(ref callback) = progress_callback {
+        if let Some(callback) = progress_callback {
             let progress = (current_row as f64 / total_rows as f64) * 100.0;
             let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(progress));
         }
@@ -177,72 +387,252 @@ pub async fn export_table_to_csv_batch(
         // 在批次之间让出控制权
         if current_row < total_rows {
             yield_to_browser().await?;
+            check_aborted(options.signal.as_ref())?;
         }
     }
 
-    // 安全地完成 CSV 写入
-    wtr.flush()
-        .map_err(|e| JsValue::from_str(&format!("完成 CSV 写入失败: {}", e)))?;
+    finish_csv(wtr, options)
+}
 
-    // 获取 CSV 数据
-    let csv_data = wtr
-        .into_inner()
-        .map_err(|e| JsValue::from_str(&format!("获取 CSV 数据失败: {}", e)))?;
+/// 分批读取行并写入 Excel 工作表
+///
+/// 分为三个阶段，每个阶段都向进度回调报告 `(进度, 阶段)`：
+/// 1. `"reading"`（0-45）：分批读取 DOM 行并展开合并单元格
+/// 2. `"writing"`（45-95）：分批将行写入工作表
+/// 3. `"saving"`（95-100）：生成 XLSX 文件，此阶段无法拆分，只在开始和结束时报告
+async fn write_xlsx_batches(
+    source: &BatchSource,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let batch_size = options.batch_size as usize;
+    let report = |progress: f64, stage: &str| report_stage(options, progress, stage);
+
+    // 1. 读取所有行；合并区域需要完整的行数据才能确定
+    let table_data = read_table_batches(source, options, 45.0).await?;
+
+    // 2. 分批写入工作表
+    let sheet_name = options.sheet_name.as_deref().unwrap_or("Sheet1");
+    let mut writer = XlsxSheetWriter::new(sheet_name, options)?;
+    let row_count = table_data.rows.len();
+    let mut current_row = 0;
+    while current_row < row_count {
+        let batch_end = std::cmp::min(current_row + batch_size, row_count);
+
+        for i in current_row..batch_end {
+            writer.write_row(&table_data, i)?;
+        }
 
-    if csv_data.get_ref().is_empty() {
-        return Err(JsValue::from_str("没有可导出的数据"));
+        current_row = batch_end;
+        report(
+            45.0 + current_row as f64 / row_count as f64 * 50.0,
+            "writing",
+        );
+        yield_to_browser().await?;
+        check_aborted(options.signal.as_ref())?;
     }
+    let worksheet = writer.finish(&table_data)?;
 
-    // 安全地创建 Blob 对象
-    let blob_property_bag = web_sys::BlobPropertyBag::new();
-    blob_property_bag.set_type("text/csv;charset=utf-8");
+    // 3. 生成文件，先让浏览器有机会渲染 95% 的进度
+    report(95.0, "saving");
+    yield_to_browser().await?;
+    check_aborted(options.signal.as_ref())?;
+    let xlsx_bytes = save_workbook(vec![worksheet])?;
+    report(100.0, "saving");
 
-    let array = js_sys::Array::of1(&js_sys::Uint8Array::from(&csv_data.get_ref()[..]));
-    let blob = Blob::new_with_u8_array_sequence_and_options(&array, &blob_property_bag)
-        .map_err(|e| JsValue::from_str(&format!("创建 Blob 对象失败: {:?}", e)))?;
+    Ok(xlsx_bytes)
+}
 
-    // 安全地创建下载链接
-    let url = Url::create_object_url_with_blob(&blob)
-        .map_err(|e| JsValue::from_str(&format!("创建下载链接失败: {:?}", e)))?;
+/// 分批读取行并生成 JSON、NDJSON、Markdown 或 ODS
+///
+/// 这些格式需要完整数据后才能生成，与 Excel 相同分阶段报告进度：
+/// `"reading"`（0-95）分批读取 DOM 行，`"saving"`（95-100）生成文件
+async fn write_document_batches(
+    source: &BatchSource,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let table_data = read_table_batches(source, options, 95.0).await?;
+
+    report_stage(options, 95.0, "saving");
+    yield_to_browser().await?;
+    check_aborted(options.signal.as_ref())?;
+
+    // 阶段进度已由本函数报告，生成时不再调用单参数的进度回调
+    let build_options = ExportOptions {
+        progress_callback: None,
+        ..options.clone()
+    };
+    let bytes = match options.format {
+        ExportFormat::Markdown => build_markdown_bytes(&table_data, &build_options)?,
+        ExportFormat::Ods => build_ods_bytes(&table_data, &build_options)?,
+        _ => build_json_bytes(&table_data, &build_options)?,
+    };
+    report_stage(options, 100.0, "saving");
+
+    Ok(bytes)
+}
 
-    // 使用 RAII 模式确保 URL 资源释放
-    let _url_guard = UrlGuard::new(&url);
+/// 分批读取表格结构并生成 HTML
+///
+/// HTML 保留原表格的结构，不展开合并单元格，也不应用表头和列选择配置；
+/// `"reading"`（0-95）分批读取 DOM 行，`"saving"`（95-100）生成文件
+async fn write_html_batches(
+    source: &BatchSource,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let total_rows = source.total_rows;
+    let batch_size = options.batch_size as usize;
+
+    options.check_html_snapshot()?;
+    report_stage(options, 0.0, "reading");
+
+    let mut reader = HtmlTableReader::new(&source.table, options.inline_styles)?;
+    let mut current_row = 0;
+    while current_row < total_rows {
+        let batch_end = std::cmp::min(current_row + batch_size, total_rows);
 
-    // 设置文件名（默认为 table_export.csv）
-    let final_filename = filename.unwrap_or_else(|| "table_export.csv".to_string());
+        for i in current_row..batch_end {
+            reader.push_row(&source.row(i)?, i)?;
+        }
 
-    // 验证文件名安全性
-    if let Err(e) = validate_filename(&final_filename) {
-        return Err(JsValue::from_str(&format!("文件名验证失败: {}", e)));
+        current_row = batch_end;
+        report_stage(
+            options,
+            current_row as f64 / total_rows as f64 * 95.0,
+            "reading",
+        );
+        yield_to_browser().await?;
+        check_aborted(options.signal.as_ref())?;
     }
 
-    let final_filename = ensure_extension(&final_filename, "csv");
+    report_stage(options, 95.0, "saving");
+    let build_options = ExportOptions {
+        progress_callback: None,
+        ..options.clone()
+    };
+    let bytes = build_html_bytes(&reader.finish(), &build_options)?;
+    report_stage(options, 100.0, "saving");
+
+    Ok(bytes)
+}
+
+/// 分批读取所有行，展开合并单元格并按配置调整表格数据
+///
+/// 读取期间以 `"reading"` 阶段报告 0 到 `progress_end` 的进度
+async fn read_table_batches(
+    source: &BatchSource,
+    options: &ExportOptions,
+    progress_end: f64,
+) -> Result<TableData, ExportError> {
+    let total_rows = source.total_rows;
+    let batch_size = options.batch_size as usize;
 
-    let anchor = document
-        .create_element("a")
-        .map_err(|e| JsValue::from_str(&format!("创建下载链接元素失败: {:?}", e)))?;
-    let anchor = anchor
-        .dyn_into::<HtmlAnchorElement>()
-        .map_err(|_| JsValue::from_str("创建的元素不是有效的锚点元素"))?;
+    // 报告初始进度
+    report_stage(options, 0.0, "reading");
 
-    anchor.set_href(&url);
-    anchor.set_download(&final_filename);
+    let reader = RowReader::new(options)?;
+    let mut span_grid = SpanGrid::new(options.span_mode);
+    let mut sections = SectionTracker::new();
+    let mut rows = Vec::with_capacity(total_rows);
+    let mut keep = Vec::with_capacity(total_rows);
+    let mut current_row = 0;
+    while current_row < total_rows {
+        let batch_end = std::cmp::min(current_row + batch_size, total_rows);
 
-    // 触发下载
-    anchor.click();
+        for i in current_row..batch_end {
+            let row = source.row(i)?;
+            sections.enter(&row, &mut span_grid);
+            rows.push(span_grid.push_row(reader.read_row(&row, i)?));
+            keep.push(!reader.is_row_hidden(&row));
+        }
 
-    Ok(JsValue::UNDEFINED)
+        current_row = batch_end;
+        report_stage(
+            options,
+            current_row as f64 / total_rows as f64 * progress_end,
+            "reading",
+        );
+        yield_to_browser().await?;
+        check_aborted(options.signal.as_ref())?;
+    }
+
+    let mut table_data = span_grid.into_table_data(rows);
+    table_data.retain_rows(&keep);
+    table_data.header_rows = source.header_rows(options, &reader)?;
+    if options.format == ExportFormat::Markdown {
+        table_data.alignments =
+            read_column_alignments(&source.table, table_data.header_rows, &reader);
+    }
+    apply_options(&mut table_data, options)?;
+    Ok(table_data)
+}
+
+/// 向进度回调报告 `(进度, 阶段)`
+fn report_stage(options: &ExportOptions, progress: f64, stage: &str) {
+    if let Some(ref callback) = options.progress_callback {
+        let _ = callback.call2(
+            &JsValue::NULL,
+            &JsValue::from_f64(progress),
+            &JsValue::from_str(stage),
+        );
+    }
+}
+
+/// 按列选择重排一行数据，规则与一次性导出相同
+///
+/// # 参数
+/// * `row` - 展开合并单元格后的行
+/// * `selection` - 解析后的列选择
+/// * `anchored` - 左上角位于该行的合并区域
+/// * `is_last_header` - 是否为最后一行表头，是则应用新表头
+fn select_columns(
+    row: &[String],
+    selection: &ColumnSelection,
+    anchored: &[MergeRange],
+    is_last_header: bool,
+) -> Vec<String> {
+    let mut selected = select_row_columns(row, &selection.columns, anchored);
+    if is_last_header {
+        for (value, title) in selected.iter_mut().zip(&selection.titles) {
+            if let Some(title) = title {
+                value.clone_from(title);
+            }
+        }
+    }
+    selected
+}
+
+/// 检查导出是否已被取消
+///
+/// 已中止时返回错误码为 `CANCELLED`、`name` 为 `"AbortError"` 的错误，调用方可以据此区分取消与失败：
+///
+/// ```javascript
+/// try {
+///     await export_table_batch_with_options('my-table', options);
+/// } catch (e) {
+///     if (e.code === 'CANCELLED') { /* 用户取消 */ }
+/// }
+/// ```
+fn check_aborted(signal: Option<&AbortSignal>) -> Result<(), ExportError> {
+    match signal {
+        Some(signal) if signal.aborted() => Err(ExportError::Cancelled),
+        _ => Ok(()),
+    }
 }
 
 /// 让出控制权给浏览器事件循环
 ///
 /// 使用 setTimeout(0) 创建一个微任务，允许浏览器处理其他事件
-async fn yield_to_browser() -> Result<(), JsValue> {
+async fn yield_to_browser() -> Result<(), ExportError> {
+    let window =
+        web_sys::window().ok_or_else(|| ExportError::browser_api(Message::WindowUnavailable))?;
     let promise = js_sys::Promise::new(&mut |resolve, _reject| {
-        let window = web_sys::window().expect("无法获取 window 对象");
         let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, 0);
     });
 
-    JsFuture::from(promise).await?;
+    JsFuture::from(promise).await.map_err(|e| {
+        ExportError::browser_api(Message::YieldFailed {
+            detail: format!("{:?}", e),
+        })
+    })?;
     Ok(())
 }
@@ -0,0 +1,84 @@
+/// 剪贴板模块
+///
+/// 将表格同时以 TSV（`text/plain`）和 HTML（`text/html`）写入系统剪贴板，
+/// 粘贴到 Excel、Google Sheets 等电子表格时保留行列结构和合并单元格
+use crate::core::{ExportOptions, clipboard_content, create_blob};
+use crate::error::ExportError;
+use crate::i18n::Message;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::ClipboardItem;
+
+/// 复制表格到剪贴板
+///
+/// 与 `export_table` 相同地提取表格数据（展开合并单元格、应用表头处理和列选择），
+/// 然后写入一个同时包含 TSV 和 HTML 的剪贴板项。不支持 `ClipboardItem` 的浏览器只写入 TSV 文本。
+/// 剪贴板 API 需要安全上下文（HTTPS 或 localhost），并且通常需要在用户操作（如点击）中调用
+///
+/// # 参数
+/// * `table_id` - 要复制的 HTML 表格元素的 ID
+/// * `options` - 导出配置（`format`、`filename`、`encoding` 和 `bom` 被忽略）
+///
+/// # 返回值
+/// * `Promise<void>` - 写入完成后兑现；失败时以带有 `code` 错误码的 `Error` 对象拒绝
+///
+/// # 示例
+/// ```javascript
+/// import { ExportOptions, copy_table_to_clipboard } from './pkg/excel_exporter.js';
+///
+/// button.addEventListener('click', async () => {
+///     await copy_table_to_clipboard('my-table', new ExportOptions());
+///     showToast('已复制，可直接粘贴到 Excel');
+/// });
+/// ```
+#[wasm_bindgen]
+pub fn copy_table_to_clipboard(table_id: String, options: &ExportOptions) -> js_sys::Promise {
+    // 在调用返回前提取表格，避免异步执行期间表格发生变化
+    let content = clipboard_content(&table_id, options);
+    let options = options.clone();
+    wasm_bindgen_futures::future_to_promise(async move {
+        let (text, html) = content.map_err(|e| options.js_error(e))?;
+        write_clipboard(&text, &html)
+            .await
+            .map_err(|e| options.js_error(e))?;
+        Ok(JsValue::UNDEFINED)
+    })
+}
+
+/// 写入剪贴板
+async fn write_clipboard(text: &str, html: &str) -> Result<(), ExportError> {
+    let window =
+        web_sys::window().ok_or_else(|| ExportError::browser_api(Message::WindowUnavailable))?;
+    let clipboard = window.navigator().clipboard();
+    // 非安全上下文中 navigator.clipboard 为 undefined
+    if clipboard.is_undefined() {
+        return Err(ExportError::browser_api(Message::ClipboardUnavailable));
+    }
+
+    let promise = match clipboard_item(text, html)? {
+        Some(item) => clipboard.write(&Array::of1(&item)),
+        None => clipboard.write_text(text),
+    };
+
+    JsFuture::from(promise).await.map_err(|e| {
+        ExportError::browser_api(Message::ClipboardWriteFailed {
+            detail: format!("{:?}", e),
+        })
+    })?;
+    Ok(())
+}
+
+/// 创建同时包含纯文本和 HTML 的剪贴板项，浏览器不支持 `ClipboardItem` 时返回 `None`
+fn clipboard_item(text: &str, html: &str) -> Result<Option<ClipboardItem>, ExportError> {
+    let record = Object::new();
+    for (mime_type, data) in [("text/plain", text), ("text/html", html)] {
+        let blob = create_blob(data.as_bytes(), mime_type)?;
+        Reflect::set(&record, &JsValue::from_str(mime_type), &blob).map_err(|e| {
+            ExportError::browser_api(Message::ClipboardWriteFailed {
+                detail: format!("{:?}", e),
+            })
+        })?;
+    }
+    Ok(ClipboardItem::new_with_record_from_str_to_blob_promise(&record).ok())
+}
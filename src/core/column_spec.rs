@@ -0,0 +1,203 @@
+/// 列配置模块
+///
+/// 按列索引或表头文本选择、排除、重排和重命名导出的列，
+/// 一次性导出和分批导出共用同一套解析规则
+use crate::error::ExportError;
+use crate::i18n::Message;
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+/// 列引用：列索引或表头文本
+///
+/// # 注意
+/// 这个类型主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnRef {
+    /// 列索引（从 0 开始，对应展开合并单元格后的列）
+    Index(usize),
+    /// 表头文本，与任一表头行中该列的文本（去除首尾空白后）相同的第一列
+    Header(String),
+}
+
+/// 列配置中的一项：要输出的列及可选的新表头
+///
+/// # 注意
+/// 这个类型主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSpec {
+    pub column: ColumnRef,
+    pub title: Option<String>,
+}
+
+/// 解析后的列选择
+///
+/// # 注意
+/// 这个类型主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSelection {
+    /// 按输出顺序排列的原始列索引
+    pub columns: Vec<usize>,
+    /// 每个输出列的新表头，`None` 表示保留原表头
+    pub titles: Vec<Option<String>>,
+}
+
+/// 按表头解析列配置
+///
+/// # 参数
+/// * `header_rows` - 表头行（展开合并单元格后），用于按表头文本查找列
+/// * `width` - 表格列数
+/// * `spec` - 要输出的列，按输出顺序排列；为空时输出全部列
+/// * `exclude` - 要排除的列，在 `spec` 之后应用
+///
+/// # 返回值
+/// * `Ok(ColumnSelection)` - 输出列及其新表头
+/// * `Err(ExportError)` - 列索引超出范围（`COLUMN_OUT_OF_RANGE`）或找不到表头（`COLUMN_NOT_FOUND`）
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn resolve_columns(
+    header_rows: &[Vec<String>],
+    width: usize,
+    spec: &[ColumnSpec],
+    exclude: &[ColumnRef],
+) -> Result<ColumnSelection, ExportError> {
+    let resolve = |column: &ColumnRef| -> Result<usize, ExportError> {
+        match column {
+            ColumnRef::Index(index) if *index < width => Ok(*index),
+            ColumnRef::Index(index) => Err(ExportError::ColumnOutOfRange {
+                column: *index,
+                width,
+            }),
+            ColumnRef::Header(header) => (0..width)
+                .find(|&col| {
+                    header_rows.iter().any(|row| {
+                        row.get(col)
+                            .is_some_and(|text| text.trim() == header.trim())
+                    })
+                })
+                .ok_or_else(|| ExportError::ColumnNotFound {
+                    header: header.clone(),
+                }),
+        }
+    };
+
+    let entries: Vec<(usize, Option<String>)> = if spec.is_empty() {
+        (0..width).map(|col| (col, None)).collect()
+    } else {
+        spec.iter()
+            .map(|entry| Ok((resolve(&entry.column)?, entry.title.clone())))
+            .collect::<Result<_, ExportError>>()?
+    };
+
+    let excluded = exclude.iter().map(resolve).collect::<Result<Vec<_>, _>>()?;
+    let (columns, titles) = entries
+        .into_iter()
+        .filter(|(col, _)| !excluded.contains(col))
+        .unzip();
+
+    Ok(ColumnSelection { columns, titles })
+}
+
+/// 解析列配置数组
+///
+/// 元素为列索引、表头文本或 `{ column, title }` 对象；`null`/`undefined` 表示未设置
+pub(crate) fn parse_column_spec(value: &JsValue) -> Result<Option<Vec<ColumnSpec>>, ExportError> {
+    parse_list(value, "column_spec", |entry| {
+        if let Some(column) = parse_column_ref(entry) {
+            return Some(ColumnSpec {
+                column,
+                title: None,
+            });
+        }
+        if !entry.is_object() {
+            return None;
+        }
+        let column = parse_column_ref(&get_property(entry, "column"))?;
+        let title = get_property(entry, "title");
+        let title = if title.is_null() || title.is_undefined() {
+            None
+        } else {
+            Some(title.as_string()?)
+        };
+        Some(ColumnSpec { column, title })
+    })
+}
+
+/// 解析要排除的列数组，元素为列索引或表头文本
+pub(crate) fn parse_column_refs(value: &JsValue) -> Result<Vec<ColumnRef>, ExportError> {
+    Ok(parse_list(value, "exclude_columns", parse_column_ref)?.unwrap_or_default())
+}
+
+/// 将列配置转换回 JavaScript 数组
+pub(crate) fn column_spec_to_js(spec: &[ColumnSpec]) -> JsValue {
+    spec.iter()
+        .map(|entry| match entry.title {
+            None => column_ref_to_js(&entry.column),
+            Some(ref title) => {
+                let object = Object::new();
+                let _ = Reflect::set(&object, &"column".into(), &column_ref_to_js(&entry.column));
+                let _ = Reflect::set(&object, &"title".into(), &title.into());
+                object.into()
+            }
+        })
+        .collect::<Array>()
+        .into()
+}
+
+/// 将列引用列表转换回 JavaScript 数组
+pub(crate) fn column_refs_to_js(refs: &[ColumnRef]) -> JsValue {
+    refs.iter().map(column_ref_to_js).collect::<Array>().into()
+}
+
+/// 逐项解析数组，任一元素无法解析时返回带位置的参数错误
+fn parse_list<T>(
+    value: &JsValue,
+    option: &'static str,
+    parse: impl Fn(&JsValue) -> Option<T>,
+) -> Result<Option<Vec<T>>, ExportError> {
+    if value.is_null() || value.is_undefined() {
+        return Ok(None);
+    }
+    let items = value
+        .dyn_ref::<Array>()
+        .ok_or_else(|| ExportError::invalid_argument(Message::ColumnListNotArray { option }))?;
+
+    items
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            parse(&entry).ok_or_else(|| {
+                ExportError::invalid_argument(Message::ColumnEntryInvalid { option, index })
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Some)
+}
+
+/// 解析单个列引用：非负整数为列索引，字符串为表头文本
+fn parse_column_ref(value: &JsValue) -> Option<ColumnRef> {
+    if let Some(header) = value.as_string() {
+        return Some(ColumnRef::Header(header));
+    }
+    value
+        .as_f64()
+        .filter(|index| *index >= 0.0 && index.fract() == 0.0)
+        .map(|index| ColumnRef::Index(index as usize))
+}
+
+/// 将列引用转换为 JavaScript 值
+fn column_ref_to_js(column: &ColumnRef) -> JsValue {
+    match column {
+        ColumnRef::Index(index) => (*index as f64).into(),
+        ColumnRef::Header(header) => header.into(),
+    }
+}
+
+/// 读取对象的属性，读取失败时视为 `undefined`
+fn get_property(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
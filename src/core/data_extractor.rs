@@ -0,0 +1,196 @@
+use js_sys::{Array, Date, Object, Reflect};
+use wasm_bindgen::JsCast;
+/// JavaScript 数据提取模块
+///
+/// 提供从 JavaScript 数组（而非 DOM 表格）中提取表格数据的功能，
+/// 用于虚拟滚动等大部分行不在 DOM 中的场景
+use wasm_bindgen::prelude::*;
+
+use super::table_extractor::TableData;
+use crate::error::ExportError;
+use crate::i18n::Message;
+use std::collections::HashMap;
+
+/// 列定义
+struct ColumnDef {
+    key: String,
+    title: String,
+}
+
+/// 从 JavaScript 数据中提取表格数据
+///
+/// # 参数
+/// * `data` - 数组的数组（每个内层数组为一行），或对象数组
+/// * `columns` - 可选的列定义列表，元素为字符串（同时作为键和表头）或 `{ key, title }` 对象。
+///   提供时会生成表头行；对象数组未提供列定义时，使用第一个对象的键作为列；
+///   数组的数组未提供列定义时，第一行即表头
+///
+/// # 返回值
+/// * `Ok(TableData)` - 表格数据，始终有一行表头
+/// * `Err(ExportError)` - 提取失败，包含错误码和上下文
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn extract_js_data(data: &JsValue, columns: Option<Array>) -> Result<TableData, ExportError> {
+    let items = data
+        .dyn_ref::<Array>()
+        .ok_or_else(|| ExportError::invalid_data(None, Message::DataNotArray))?;
+
+    if items.length() == 0 {
+        return Err(ExportError::EmptyTable);
+    }
+
+    let mut columns = match columns {
+        Some(columns) => Some(parse_columns(&columns)?),
+        None => None,
+    };
+
+    // 对象数组未提供列定义时，使用第一个对象的键
+    if columns.is_none() {
+        let first = items.get(0);
+        if first.is_object() && !Array::is_array(&first) {
+            columns = Some(
+                Object::keys(first.unchecked_ref::<Object>())
+                    .iter()
+                    .filter_map(|key| key.as_string())
+                    .map(|key| ColumnDef {
+                        title: key.clone(),
+                        key,
+                    })
+                    .collect(),
+            );
+        }
+    }
+
+    let mut rows = Vec::with_capacity(items.length() as usize + 1);
+
+    if let Some(ref columns) = columns {
+        rows.push(columns.iter().map(|c| c.title.clone()).collect());
+    }
+
+    for (i, item) in items.iter().enumerate() {
+        let row = if let Some(values) = item.dyn_ref::<Array>() {
+            values.iter().map(|v| js_value_to_text(&v)).collect()
+        } else if item.is_object() {
+            let columns = columns.as_ref().ok_or_else(|| {
+                ExportError::invalid_data(Some(i), Message::ObjectRowsNeedColumns)
+            })?;
+            columns
+                .iter()
+                .map(|c| {
+                    Reflect::get(&item, &JsValue::from_str(&c.key)).map(|v| js_value_to_text(&v))
+                })
+                .collect::<Result<Vec<String>, JsValue>>()
+                .map_err(|e| {
+                    ExportError::invalid_data(
+                        Some(i),
+                        Message::RowReadFailed {
+                            row: i,
+                            detail: format!("{:?}", e),
+                        },
+                    )
+                })?
+        } else {
+            return Err(ExportError::invalid_data(
+                Some(i),
+                Message::RowNotArrayOrObject { row: i },
+            ));
+        };
+        rows.push(row);
+    }
+
+    // 补齐为矩形矩阵
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in rows.iter_mut() {
+        row.resize(width, String::new());
+    }
+
+    Ok(TableData {
+        rows,
+        merges: Vec::new(),
+        // 由列定义生成的表头行，或数组的数组的第一行
+        header_rows: 1,
+        alignments: Vec::new(),
+        cell_types: HashMap::new(),
+        links: HashMap::new(),
+    })
+}
+
+/// 解析列定义列表
+fn parse_columns(columns: &Array) -> Result<Vec<ColumnDef>, ExportError> {
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            if let Some(key) = column.as_string() {
+                return Ok(ColumnDef {
+                    title: key.clone(),
+                    key,
+                });
+            }
+
+            let key = Reflect::get(&column, &JsValue::from_str("key"))
+                .ok()
+                .and_then(|v| v.as_string())
+                .ok_or_else(|| {
+                    ExportError::invalid_data(None, Message::ColumnKeyMissing { index: i })
+                })?;
+            let title = Reflect::get(&column, &JsValue::from_str("title"))
+                .ok()
+                .and_then(|v| v.as_string())
+                .unwrap_or_else(|| key.clone());
+
+            Ok(ColumnDef { key, title })
+        })
+        .collect()
+}
+
+/// 将 JavaScript 值转换为单元格文本
+///
+/// `null`/`undefined` 为空字符串，日期转换为 ISO 字符串以便 Excel 导出识别为日期，
+/// 其他对象序列化为 JSON
+fn js_value_to_text(value: &JsValue) -> String {
+    if value.is_null() || value.is_undefined() {
+        return String::new();
+    }
+    if let Some(text) = value.as_string() {
+        return text;
+    }
+    if let Some(number) = value.as_f64() {
+        return format_number(number);
+    }
+    if let Some(flag) = value.as_bool() {
+        return flag.to_string();
+    }
+    if let Some(date) = value.dyn_ref::<Date>() {
+        // 无效日期调用 toISOString 会抛出异常
+        if date.get_time().is_nan() {
+            return String::new();
+        }
+        return String::from(date.to_iso_string());
+    }
+    // 函数等无法序列化的值会得到 undefined
+    js_sys::JSON::stringify(value)
+        .ok()
+        .and_then(|json| json.as_string())
+        .unwrap_or_default()
+}
+
+/// 将数字格式化为单元格文本
+///
+/// 整数不带小数点，NaN 和无穷大为空字符串
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn format_number(number: f64) -> String {
+    if !number.is_finite() {
+        String::new()
+    } else if number == 0.0 {
+        // 避免输出 "-0"
+        "0".to_string()
+    } else {
+        format!("{}", number)
+    }
+}
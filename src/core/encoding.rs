@@ -0,0 +1,112 @@
+/// CSV 文本编码模块
+///
+/// 将 CSV 写入器生成的 UTF-8 文本转换为指定编码，并按需添加 BOM，
+/// 使 Windows 上的 Excel 等工具能够正确识别中文、日文等非 ASCII 字符
+use crate::error::ExportError;
+use encoding_rs::{BIG5, Encoding, GB18030, GBK, SHIFT_JIS};
+use wasm_bindgen::prelude::*;
+
+/// UTF-8 字节顺序标记
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+
+/// UTF-16LE 字节顺序标记
+const UTF16LE_BOM: [u8; 2] = [0xFF, 0xFE];
+
+/// CSV 文件编码
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvEncoding {
+    /// UTF-8（默认），可通过 `bom` 选项添加 BOM
+    #[default]
+    Utf8,
+    /// GBK（简体中文）
+    Gbk,
+    /// GB18030（简体中文，可表示所有 Unicode 字符）
+    Gb18030,
+    /// Shift_JIS（日文）
+    ShiftJis,
+    /// Big5（繁体中文）
+    Big5,
+    /// UTF-16LE，始终带有 BOM
+    Utf16Le,
+}
+
+impl CsvEncoding {
+    /// 编码名称，用于 MIME 类型的 charset 参数
+    pub fn label(self) -> &'static str {
+        match self {
+            CsvEncoding::Utf8 => "utf-8",
+            CsvEncoding::Gbk => "gbk",
+            CsvEncoding::Gb18030 => "gb18030",
+            CsvEncoding::ShiftJis => "shift_jis",
+            CsvEncoding::Big5 => "big5",
+            CsvEncoding::Utf16Le => "utf-16le",
+        }
+    }
+}
+
+/// 将 UTF-8 文本转换为指定编码
+///
+/// # 参数
+/// * `utf8` - UTF-8 编码的文本字节
+/// * `encoding` - 目标编码
+/// * `bom` - 是否添加 UTF-8 BOM（仅对 Utf8 生效，UTF-16LE 始终带 BOM，传统编码没有 BOM）
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - 转换后的字节
+/// * `Err(ExportError)` - 文本包含目标编码无法表示的字符，错误码为 `ENCODING_FAILED`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn encode_text(
+    utf8: Vec<u8>,
+    encoding: CsvEncoding,
+    bom: bool,
+) -> Result<Vec<u8>, ExportError> {
+    match encoding {
+        CsvEncoding::Utf8 if bom => {
+            let mut bytes = Vec::with_capacity(UTF8_BOM.len() + utf8.len());
+            bytes.extend_from_slice(&UTF8_BOM);
+            bytes.extend_from_slice(&utf8);
+            Ok(bytes)
+        }
+        CsvEncoding::Utf8 => Ok(utf8),
+        CsvEncoding::Utf16Le => {
+            let text = String::from_utf8_lossy(&utf8);
+            let mut bytes = Vec::with_capacity(UTF16LE_BOM.len() + utf8.len() * 2);
+            bytes.extend_from_slice(&UTF16LE_BOM);
+            for unit in text.encode_utf16() {
+                bytes.extend_from_slice(&unit.to_le_bytes());
+            }
+            Ok(bytes)
+        }
+        CsvEncoding::Gbk => encode_legacy(&utf8, GBK, encoding),
+        CsvEncoding::Gb18030 => encode_legacy(&utf8, GB18030, encoding),
+        CsvEncoding::ShiftJis => encode_legacy(&utf8, SHIFT_JIS, encoding),
+        CsvEncoding::Big5 => encode_legacy(&utf8, BIG5, encoding),
+    }
+}
+
+/// 使用 encoding_rs 转换为传统多字节编码
+///
+/// encoding_rs 会将无法表示的字符替换为 HTML 数字实体，这里改为报错，避免静默损坏数据
+fn encode_legacy(
+    utf8: &[u8],
+    target: &'static Encoding,
+    encoding: CsvEncoding,
+) -> Result<Vec<u8>, ExportError> {
+    let text = String::from_utf8_lossy(utf8);
+    let (bytes, _, had_errors) = target.encode(&text);
+    if had_errors {
+        let character = text
+            .chars()
+            .find(|ch| target.encode(ch.encode_utf8(&mut [0; 4])).2)
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        return Err(ExportError::EncodingFailed {
+            encoding: encoding.label(),
+            character,
+        });
+    }
+    Ok(bytes.into_owned())
+}
@@ -1,115 +1,164 @@
 /// CSV 导出模块
 ///
 /// 提供 CSV 格式的表格导出功能
-use crate::resource::UrlGuard;
-use crate::validation::{ensure_extension, validate_filename};
-use csv::Writer;
+use super::ExportFormat;
+use super::encoding::encode_text;
+use super::options::ExportOptions;
+use super::table_extractor::TableData;
+use crate::error::ExportError;
+use csv::{QuoteStyle, Terminator, Writer, WriterBuilder};
 use std::io::Cursor;
 use wasm_bindgen::prelude::*;
-use web_sys::{Blob, HtmlAnchorElement, Url};
 
-/// 导出为 CSV 格式
+/// CSV 字段的引号策略
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CsvQuoteStyle {
+    /// 仅在字段包含分隔符、引号或换行时加引号（默认）
+    #[default]
+    Necessary,
+    /// 所有字段都加引号
+    Always,
+    /// 所有非数字字段都加引号
+    NonNumeric,
+    /// 从不加引号（字段包含特殊字符时输出可能无法正确解析）
+    Never,
+}
+
+impl From<CsvQuoteStyle> for QuoteStyle {
+    fn from(style: CsvQuoteStyle) -> Self {
+        match style {
+            CsvQuoteStyle::Necessary => QuoteStyle::Necessary,
+            CsvQuoteStyle::Always => QuoteStyle::Always,
+            CsvQuoteStyle::NonNumeric => QuoteStyle::NonNumeric,
+            CsvQuoteStyle::Never => QuoteStyle::Never,
+        }
+    }
+}
+
+/// CSV 记录之间的换行符
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineTerminator {
+    /// `\r\n`（默认，与 Excel 一致）
+    #[default]
+    Crlf,
+    /// `\n`
+    Lf,
+    /// `\r`
+    Cr,
+}
+
+impl From<LineTerminator> for Terminator {
+    fn from(terminator: LineTerminator) -> Self {
+        match terminator {
+            LineTerminator::Crlf => Terminator::CRLF,
+            LineTerminator::Lf => Terminator::Any(b'\n'),
+            LineTerminator::Cr => Terminator::Any(b'\r'),
+        }
+    }
+}
+
+/// 按配置中的 CSV 方言创建写入器
+///
+/// 一次性导出和分批导出共用，保证两条路径输出的格式一致。
+/// TSV 格式始终使用制表符分隔，其余方言设置与 CSV 相同。
+/// 分批导出时比之前所有行都宽的行无法补齐之前的行，因此允许记录长度不同，而不是导出失败
+///
+/// # 参数
+/// * `options` - 导出配置（使用其中的分隔符、引号、引号策略、换行符和转义设置）
+pub(crate) fn csv_writer(options: &ExportOptions) -> Writer<Cursor<Vec<u8>>> {
+    let delimiter = match options.format {
+        ExportFormat::Tsv => b'\t',
+        _ => options.delimiter,
+    };
+    WriterBuilder::new()
+        .delimiter(delimiter)
+        .quote(options.quote)
+        .quote_style(options.quote_style.into())
+        .terminator(options.line_terminator.into())
+        .double_quote(options.double_quote)
+        .escape(options.escape)
+        .flexible(true)
+        .from_writer(Cursor::new(Vec::new()))
+}
+
+/// 生成 CSV（或 TSV）文件内容
 ///
 /// # 参数
-/// * `table_data` - 表格数据（二维字符串数组）
-/// * `filename` - 可选的导出文件名
-/// * `progress_callback` - 可选的进度回调函数
+/// * `table_data` - 表格数据
+/// * `options` - 导出配置（使用其中的 CSV 方言、编码设置和进度回调）
 ///
 /// # 返回值
-/// * `Ok(())` - 导出成功
-/// * `Err(JsValue)` - 导出失败，包含错误信息
-pub fn export_as_csv(
-    table_data: Vec<Vec<String>>,
-    filename: Option<String>,
-    progress_callback: Option<js_sys::Function>,
-) -> Result<(), JsValue> {
-    let total_rows = table_data.len();
+/// * `Ok(Vec<u8>)` - CSV 文件字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+pub fn build_csv_bytes(
+    table_data: &TableData,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let total_rows = table_data.rows.len();
+    let progress_callback = options.progress_callback.as_ref();
 
     // 报告初始进度
-    if let Some(ref callback) = progress_callback {
+    if let Some(callback) = progress_callback {
         let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(0.0));
     }
 
     // 创建一个 CSV 写入器
-    let mut wtr = Writer::from_writer(Cursor::new(Vec::new()));
+    let mut wtr = csv_writer(options);
 
     // 写入所有数据，并报告进度
-    for (index, row_data) in table_data.into_iter().enumerate() {
-        wtr.write_record(&row_data)
-            .map_err(|e| JsValue::from_str(&format!("写入 CSV 数据失败: {}", e)))?;
+    for (index, row_data) in table_data.rows.iter().enumerate() {
+        wtr.write_record(row_data)
+            .map_err(|e| ExportError::CsvWriteFailed {
+                row: Some(index),
+                message: e.to_string(),
+            })?;
 
         // 定期报告进度（每10行或最后一行）
-        if let Some(ref callback) = progress_callback
-            && (index % 10 == 0 || index == total_rows - 1) {
-                let progress = ((index + 1) as f64 / total_rows as f64) * 100.0;
-                let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(progress));
-            }
+        if let Some(callback) = progress_callback
+            && (index % 10 == 0 || index == total_rows - 1)
+        {
+            let progress = ((index + 1) as f64 / total_rows as f64) * 100.0;
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(progress));
+        }
     }
 
-    // 安全地完成 CSV 写入
-    wtr.flush()
-        .map_err(|e| JsValue::from_str(&format!("完成 CSV 写入失败: {}", e)))?;
-
-    // 获取 CSV 数据
-    let csv_data = wtr
-        .into_inner()
-        .map_err(|e| JsValue::from_str(&format!("获取 CSV 数据失败: {}", e)))?;
-
-    if csv_data.get_ref().is_empty() {
-        return Err(JsValue::from_str("没有可导出的数据"));
-    }
-
-    // 创建并下载文件
-    create_and_download_csv(csv_data.get_ref(), filename)
+    finish_csv(wtr, options)
 }
 
-/// 创建 CSV Blob 并触发下载
+/// 完成 CSV 写入，并按配置转换编码
+///
+/// 一次性导出和分批导出共用
 ///
 /// # 参数
-/// * `data` - CSV 数据字节
-/// * `filename` - 可选的导出文件名
-fn create_and_download_csv(data: &[u8], filename: Option<String>) -> Result<(), JsValue> {
-    let window = web_sys::window().ok_or_else(|| JsValue::from_str("无法获取 window 对象"))?;
-    let document = window
-        .document()
-        .ok_or_else(|| JsValue::from_str("无法获取 document 对象"))?;
-
-    // 创建 CSV Blob 对象
-    let blob_property_bag = web_sys::BlobPropertyBag::new();
-    blob_property_bag.set_type("text/csv;charset=utf-8");
-
-    let array = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
-    let blob = Blob::new_with_u8_array_sequence_and_options(&array, &blob_property_bag)
-        .map_err(|e| JsValue::from_str(&format!("创建 Blob 对象失败: {:?}", e)))?;
-
-    // 创建下载链接
-    let url = Url::create_object_url_with_blob(&blob)
-        .map_err(|e| JsValue::from_str(&format!("创建下载链接失败: {:?}", e)))?;
-
-    // 使用 RAII 模式确保 URL 资源释放
-    let _url_guard = UrlGuard::new(&url);
-
-    // 设置文件名
-    let final_filename = filename.unwrap_or_else(|| "table_export.csv".to_string());
+/// * `wtr` - 已写入所有行的 CSV 写入器
+/// * `options` - 导出配置（使用其中的编码和 BOM 设置）
+pub(crate) fn finish_csv(
+    mut wtr: Writer<Cursor<Vec<u8>>>,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    // 安全地完成 CSV 写入
+    wtr.flush().map_err(|e| ExportError::CsvWriteFailed {
+        row: None,
+        message: e.to_string(),
+    })?;
 
-    // 验证文件名安全性
-    if let Err(e) = validate_filename(&final_filename) {
-        return Err(JsValue::from_str(&format!("文件名验证失败: {}", e)));
+    // 获取 CSV 数据
+    let csv_data = wtr
+        .into_inner()
+        .map_err(|e| ExportError::CsvWriteFailed {
+            row: None,
+            message: e.to_string(),
+        })?
+        .into_inner();
+
+    if csv_data.is_empty() {
+        return Err(ExportError::EmptyTable);
     }
 
-    let final_filename = ensure_extension(&final_filename, "csv");
-
-    // 创建下载链接元素
-    let anchor = document
-        .create_element("a")
-        .map_err(|e| JsValue::from_str(&format!("创建下载链接元素失败: {:?}", e)))?;
-    let anchor = anchor
-        .dyn_into::<HtmlAnchorElement>()
-        .map_err(|_| JsValue::from_str("创建的元素不是有效的锚点元素"))?;
-
-    anchor.set_href(&url);
-    anchor.set_download(&final_filename);
-    anchor.click();
-
-    Ok(())
+    encode_text(csv_data, options.encoding, options.bom)
 }
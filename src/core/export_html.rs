@@ -0,0 +1,431 @@
+/// HTML 导出模块
+///
+/// 生成可以独立打开的 HTML 文件，保留表格的 thead/tbody/tfoot 结构和合并单元格。
+/// 只复制单元格文本和白名单中的计算样式，原表格中的脚本、事件处理器和其他属性不会被带入
+use super::form_controls::cell_text;
+use super::options::ExportOptions;
+use super::table_extractor::{MergeSpans, TableData};
+use crate::error::ExportError;
+use crate::i18n::Message;
+use std::fmt::Write as _;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{Element, HtmlTableCellElement, HtmlTableElement, HtmlTableRowElement, Window};
+
+/// 表格元素内联的计算样式属性
+const TABLE_STYLE_PROPERTIES: &[&str] = &[
+    "border-collapse",
+    "border-spacing",
+    "width",
+    "color",
+    "background-color",
+    "font-family",
+    "font-size",
+];
+
+/// 行元素内联的计算样式属性
+const ROW_STYLE_PROPERTIES: &[&str] = &["height", "color", "background-color"];
+
+/// 单元格元素内联的计算样式属性
+const CELL_STYLE_PROPERTIES: &[&str] = &[
+    "color",
+    "background-color",
+    "font-family",
+    "font-size",
+    "font-weight",
+    "font-style",
+    "text-align",
+    "vertical-align",
+    "text-decoration-line",
+    "white-space",
+    "padding-top",
+    "padding-right",
+    "padding-bottom",
+    "padding-left",
+    "border-top-width",
+    "border-top-style",
+    "border-top-color",
+    "border-right-width",
+    "border-right-style",
+    "border-right-color",
+    "border-bottom-width",
+    "border-bottom-style",
+    "border-bottom-color",
+    "border-left-width",
+    "border-left-style",
+    "border-left-color",
+];
+
+/// 未内联样式时使用的默认样式
+const DEFAULT_STYLE: &str =
+    "table{border-collapse:collapse}th,td{border:1px solid #999;padding:4px 8px}";
+
+/// 表格分区
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HtmlSectionKind {
+    /// `<thead>`
+    Head,
+    /// `<tbody>`
+    Body,
+    /// `<tfoot>`
+    Foot,
+}
+
+impl HtmlSectionKind {
+    fn tag(self) -> &'static str {
+        match self {
+            HtmlSectionKind::Head => "thead",
+            HtmlSectionKind::Body => "tbody",
+            HtmlSectionKind::Foot => "tfoot",
+        }
+    }
+}
+
+/// 保留结构的表格，用于生成 HTML
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HtmlTable {
+    /// `<caption>` 文本
+    pub caption: Option<String>,
+    /// 表格元素的内联样式
+    pub style: Option<String>,
+    pub sections: Vec<HtmlSection>,
+}
+
+/// 表格分区及其中的行
+#[derive(Debug, Clone, PartialEq)]
+pub struct HtmlSection {
+    pub kind: HtmlSectionKind,
+    pub rows: Vec<HtmlRow>,
+}
+
+/// 表格行
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HtmlRow {
+    /// 行元素的内联样式
+    pub style: Option<String>,
+    pub cells: Vec<HtmlCell>,
+}
+
+/// 单元格（未展开合并单元格，与 HTML 中的 `<td>`/`<th>` 一一对应）
+#[derive(Debug, Clone, PartialEq)]
+pub struct HtmlCell {
+    /// 是否为 `<th>`
+    pub header: bool,
+    pub text: String,
+    pub colspan: usize,
+    pub rowspan: usize,
+    /// 单元格元素的内联样式
+    pub style: Option<String>,
+}
+
+impl HtmlTable {
+    /// 由提取后的表格数据构建（用于没有 DOM 表格的 JavaScript 数据导出）
+    ///
+    /// 表头行放入 `<thead>` 并使用 `<th>`，合并区域还原为 colspan/rowspan
+    pub fn from_table_data(table_data: &TableData) -> Self {
+        let spans = MergeSpans::new(table_data);
+        let header_rows = table_data.header_rows.min(table_data.rows.len());
+
+        let build_rows = |range: std::ops::Range<usize>, header: bool| -> Vec<HtmlRow> {
+            range
+                .map(|row| HtmlRow {
+                    style: None,
+                    cells: table_data.rows[row]
+                        .iter()
+                        .enumerate()
+                        .filter(|(col, _)| !spans.covered.contains(&(row, *col)))
+                        .map(|(col, text)| {
+                            let (rowspan, colspan) =
+                                spans.anchors.get(&(row, col)).copied().unwrap_or((1, 1));
+                            HtmlCell {
+                                header,
+                                text: text.clone(),
+                                colspan,
+                                rowspan,
+                                style: None,
+                            }
+                        })
+                        .collect(),
+                })
+                .collect()
+        };
+
+        let mut sections = Vec::new();
+        if header_rows > 0 {
+            sections.push(HtmlSection {
+                kind: HtmlSectionKind::Head,
+                rows: build_rows(0..header_rows, true),
+            });
+        }
+        if header_rows < table_data.rows.len() {
+            sections.push(HtmlSection {
+                kind: HtmlSectionKind::Body,
+                rows: build_rows(header_rows..table_data.rows.len(), false),
+            });
+        }
+
+        Self {
+            caption: None,
+            style: None,
+            sections,
+        }
+    }
+}
+
+/// 生成 HTML 文件内容
+///
+/// # 参数
+/// * `table` - 保留结构的表格
+/// * `options` - 导出配置（使用其中的文件名、是否内联样式和进度回调）
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - UTF-8 编码的 HTML 字节
+/// * `Err(ExportError)` - 表格没有任何行时返回 `EMPTY_TABLE`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn build_html_bytes(
+    table: &HtmlTable,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let progress_callback = options.progress_callback.as_ref();
+
+    // 报告初始进度
+    if let Some(callback) = progress_callback {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(0.0));
+    }
+
+    if table.sections.iter().all(|section| section.rows.is_empty()) {
+        return Err(ExportError::EmptyTable);
+    }
+
+    // 标题依次取表格标题、文件名（不含扩展名）和默认文件名
+    let filename = options
+        .filename
+        .as_deref()
+        .map(|name| name.strip_suffix(".html").unwrap_or(name));
+    let title = table
+        .caption
+        .as_deref()
+        .or(filename)
+        .unwrap_or(options.resolved_locale().default_file_stem());
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(html, "<title>{}</title>", escape_text(title));
+    if !options.inline_styles {
+        let _ = writeln!(html, "<style>{}</style>", DEFAULT_STYLE);
+    }
+    html.push_str("</head>\n<body>\n");
+
+    html.push_str("<table");
+    push_style(&mut html, table.style.as_deref());
+    html.push_str(">\n");
+    if let Some(ref caption) = table.caption {
+        let _ = writeln!(html, "<caption>{}</caption>", escape_text(caption));
+    }
+
+    for section in table
+        .sections
+        .iter()
+        .filter(|section| !section.rows.is_empty())
+    {
+        let _ = writeln!(html, "<{}>", section.kind.tag());
+        for row in &section.rows {
+            html.push_str("<tr");
+            push_style(&mut html, row.style.as_deref());
+            html.push('>');
+            for cell in &row.cells {
+                let tag = if cell.header { "th" } else { "td" };
+                let _ = write!(html, "<{}", tag);
+                if cell.colspan > 1 {
+                    let _ = write!(html, " colspan=\"{}\"", cell.colspan);
+                }
+                if cell.rowspan > 1 {
+                    let _ = write!(html, " rowspan=\"{}\"", cell.rowspan);
+                }
+                push_style(&mut html, cell.style.as_deref());
+                let _ = write!(html, ">{}</{}>", escape_text(&cell.text), tag);
+            }
+            html.push_str("</tr>\n");
+        }
+        let _ = writeln!(html, "</{}>", section.kind.tag());
+    }
+
+    html.push_str("</table>\n</body>\n</html>\n");
+
+    if let Some(callback) = progress_callback {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(100.0));
+    }
+
+    Ok(html.into_bytes())
+}
+
+/// 写入 `style` 属性
+fn push_style(html: &mut String, style: Option<&str>) {
+    if let Some(style) = style {
+        let _ = write!(html, " style=\"{}\"", escape_attribute(style));
+    }
+}
+
+/// 转义文本内容，换行转换为 `<br>`
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\r' => {
+                chars.next_if_eq(&'\n');
+                escaped.push_str("<br>");
+            }
+            '\n' => escaped.push_str("<br>"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// 转义属性值
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// 逐行读取 DOM 表格的结构
+///
+/// 一次性导出和分批导出共用，分批导出可以在两次 `push_row` 之间让出控制权
+pub(crate) struct HtmlTableReader {
+    /// 需要内联样式时用于计算样式
+    window: Option<Window>,
+    table: HtmlTable,
+    /// 上一行所在的分区元素，用于判断是否开始新的分区
+    last_parent: Option<Element>,
+}
+
+impl HtmlTableReader {
+    /// 读取表格标题和表格样式
+    ///
+    /// # 参数
+    /// * `table` - 表格元素
+    /// * `inline_styles` - 是否内联计算样式
+    pub(crate) fn new(table: &HtmlTableElement, inline_styles: bool) -> Result<Self, ExportError> {
+        let window = if inline_styles {
+            Some(
+                web_sys::window()
+                    .ok_or_else(|| ExportError::browser_api(Message::WindowUnavailable))?,
+            )
+        } else {
+            None
+        };
+
+        let caption = table
+            .caption()
+            .map(|caption| caption.inner_text().trim().to_string())
+            .filter(|caption| !caption.is_empty());
+        let style = window
+            .as_ref()
+            .and_then(|window| computed_style(window, table, TABLE_STYLE_PROPERTIES));
+
+        Ok(Self {
+            window,
+            table: HtmlTable {
+                caption,
+                style,
+                sections: Vec::new(),
+            },
+            last_parent: None,
+        })
+    }
+
+    /// 读取一行
+    ///
+    /// # 参数
+    /// * `row` - 表格行元素
+    /// * `row_index` - 行索引（从 0 开始，仅用于错误信息）
+    pub(crate) fn push_row(
+        &mut self,
+        row: &HtmlTableRowElement,
+        row_index: usize,
+    ) -> Result<(), ExportError> {
+        let parent = row.parent_element();
+        let same_section = match (&parent, &self.last_parent) {
+            (Some(parent), Some(last)) => parent.is_same_node(Some(last)),
+            _ => false,
+        };
+        if !same_section || self.table.sections.is_empty() {
+            let kind = match parent.as_ref().map(|p| p.tag_name().to_ascii_lowercase()) {
+                Some(tag) if tag == "thead" => HtmlSectionKind::Head,
+                Some(tag) if tag == "tfoot" => HtmlSectionKind::Foot,
+                _ => HtmlSectionKind::Body,
+            };
+            self.table.sections.push(HtmlSection {
+                kind,
+                rows: Vec::new(),
+            });
+        }
+        self.last_parent = parent;
+
+        let cells = row.cells();
+        let mut html_row = HtmlRow {
+            style: self
+                .window
+                .as_ref()
+                .and_then(|window| computed_style(window, row, ROW_STYLE_PROPERTIES)),
+            cells: Vec::with_capacity(cells.length() as usize),
+        };
+
+        for j in 0..cells.length() {
+            let read_failed = || ExportError::ReadFailed {
+                row: row_index,
+                column: Some(j as usize),
+            };
+            let cell = cells
+                .get_with_index(j)
+                .ok_or_else(read_failed)?
+                .dyn_into::<HtmlTableCellElement>()
+                .map_err(|_| read_failed())?;
+
+            html_row.cells.push(HtmlCell {
+                header: cell.tag_name().eq_ignore_ascii_case("th"),
+                text: cell_text(&cell).0,
+                colspan: (cell.col_span() as usize).max(1),
+                rowspan: (cell.row_span() as usize).max(1),
+                style: self
+                    .window
+                    .as_ref()
+                    .and_then(|window| computed_style(window, &cell, CELL_STYLE_PROPERTIES)),
+            });
+        }
+
+        if let Some(section) = self.table.sections.last_mut() {
+            section.rows.push(html_row);
+        }
+        Ok(())
+    }
+
+    /// 返回读取完成的表格
+    pub(crate) fn finish(self) -> HtmlTable {
+        self.table
+    }
+}
+
+/// 读取元素的计算样式，只保留指定的属性
+fn computed_style(window: &Window, element: &Element, properties: &[&str]) -> Option<String> {
+    let style = window.get_computed_style(element).ok().flatten()?;
+    let mut css = String::new();
+    for name in properties {
+        if let Ok(value) = style.get_property_value(name)
+            && !value.is_empty()
+        {
+            let _ = write!(css, "{}:{};", name, value);
+        }
+    }
+    (!css.is_empty()).then_some(css)
+}
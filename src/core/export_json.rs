@@ -0,0 +1,166 @@
+/// JSON 导出模块
+///
+/// 提供 JSON 和 NDJSON（每行一个 JSON 值）格式的表格导出功能
+use super::ExportFormat;
+use super::options::ExportOptions;
+use super::table_extractor::TableData;
+use super::type_inference::{CellType, CellValue, resolve_cell_value};
+use crate::error::ExportError;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use wasm_bindgen::prelude::*;
+
+/// JSON 中每一行的表示方式
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JsonLayout {
+    /// 以表头为键的对象（默认），表头行本身不输出
+    #[default]
+    Objects,
+    /// 数组，表头行作为普通行输出
+    Arrays,
+}
+
+/// 生成 JSON 或 NDJSON 文件内容
+///
+/// # 参数
+/// * `table_data` - 表格数据
+/// * `options` - 导出配置（使用其中的格式、JSON 布局、类型推断、列类型和进度回调）
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - UTF-8 编码的 JSON 字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn build_json_bytes(
+    table_data: &TableData,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let total_rows = table_data.rows.len();
+    let header_rows = table_data.header_rows.min(total_rows);
+    let progress_callback = options.progress_callback.as_ref();
+
+    // 报告初始进度
+    if let Some(callback) = progress_callback {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(0.0));
+    }
+
+    let (keys, first_row) = match options.json_layout {
+        JsonLayout::Objects => (Some(object_keys(table_data)), header_rows),
+        JsonLayout::Arrays => (None, 0),
+    };
+    let ndjson = options.format == ExportFormat::Ndjson;
+
+    let mut output = Vec::new();
+    let mut records = Vec::with_capacity(total_rows - first_row);
+
+    for index in first_row..total_rows {
+        let infer = options.json_infer_types && index >= header_rows;
+        let values = table_data.rows[index]
+            .iter()
+            .enumerate()
+            .map(|(col, text)| {
+                cell_json(
+                    text,
+                    infer,
+                    table_data.cell_type(index, col, &options.column_types),
+                )
+            });
+
+        let record = match keys {
+            Some(ref keys) => {
+                Value::Object(keys.iter().cloned().zip(values).collect::<Map<_, _>>())
+            }
+            None => Value::Array(values.collect()),
+        };
+
+        if ndjson {
+            serde_json::to_writer(&mut output, &record).map_err(json_write_failed)?;
+            output.push(b'\n');
+        } else {
+            records.push(record);
+        }
+
+        // 定期报告进度（每10行或最后一行）
+        if let Some(callback) = progress_callback
+            && (index % 10 == 0 || index == total_rows - 1)
+        {
+            let progress = ((index + 1) as f64 / total_rows as f64) * 100.0;
+            let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(progress));
+        }
+    }
+
+    if !ndjson {
+        serde_json::to_writer(&mut output, &records).map_err(json_write_failed)?;
+    }
+
+    Ok(output)
+}
+
+/// 对象的键：取最后一个表头行的文本
+///
+/// 没有表头行或表头为空的列使用 `column_<列号>`，重复的表头添加 `_2`、`_3` 等后缀
+fn object_keys(table_data: &TableData) -> Vec<String> {
+    let width = table_data.rows.first().map_or(0, Vec::len);
+    let header = table_data
+        .header_rows
+        .checked_sub(1)
+        .and_then(|last| table_data.rows.get(last));
+
+    let mut seen = HashSet::with_capacity(width);
+    (0..width)
+        .map(|col| {
+            let text = header.map_or("", |row| row[col].trim());
+            let base = if text.is_empty() {
+                format!("column_{}", col + 1)
+            } else {
+                text.to_string()
+            };
+
+            let mut key = base.clone();
+            let mut suffix = 2;
+            while !seen.insert(key.clone()) {
+                key = format!("{}_{}", base, suffix);
+                suffix += 1;
+            }
+            key
+        })
+        .collect()
+}
+
+/// 将单元格文本转换为 JSON 值
+///
+/// 启用类型推断时，数字、百分比（转换为小数，如 `12.5%` 为 `0.125`）和布尔值输出为对应的 JSON 类型，
+/// 其余（包括日期）保持字符串；类型为 Text 的列或单元格始终为字符串
+fn cell_json(text: &str, infer: bool, cell_type: CellType) -> Value {
+    if !infer {
+        return Value::String(text.to_string());
+    }
+
+    match resolve_cell_value(text, cell_type) {
+        CellValue::Number { value, .. } | CellValue::Percent { value, .. } => {
+            number_json(value).unwrap_or_else(|| Value::String(text.to_string()))
+        }
+        CellValue::Boolean(value) => Value::Bool(value),
+        _ => Value::String(text.to_string()),
+    }
+}
+
+/// 将数字转换为 JSON 值，整数不带小数部分
+fn number_json(value: f64) -> Option<Value> {
+    // 2^53 以内的整数可以精确表示为 i64
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+    if value.fract() == 0.0 && value.abs() <= MAX_SAFE_INTEGER {
+        return Some(Value::from(value as i64));
+    }
+    serde_json::Number::from_f64(value).map(Value::Number)
+}
+
+/// 将 serde_json 的错误转换为导出错误
+fn json_write_failed(error: serde_json::Error) -> ExportError {
+    ExportError::JsonWriteFailed {
+        message: error.to_string(),
+    }
+}
@@ -0,0 +1,149 @@
+/// Markdown 导出模块
+///
+/// 提供 GitHub 风格（GFM）Markdown 表格的导出功能
+use super::options::ExportOptions;
+use super::table_extractor::{ColumnAlign, TableData};
+use super::type_inference::{CellValue, resolve_cell_value};
+use crate::error::ExportError;
+use wasm_bindgen::prelude::*;
+
+/// 生成 Markdown 表格文本
+///
+/// GFM 表格只有一行表头：多行表头按列合并为 `上层 / 下层`，没有表头行时表头为空。
+/// 列的对齐方式优先取 CSS `text-align`，未指定时数字列右对齐
+///
+/// # 参数
+/// * `table_data` - 表格数据
+/// * `options` - 导出配置（使用其中的列类型和进度回调）
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - UTF-8 编码的 Markdown 字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn build_markdown_bytes(
+    table_data: &TableData,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let progress_callback = options.progress_callback.as_ref();
+
+    // 报告初始进度
+    if let Some(callback) = progress_callback {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(0.0));
+    }
+
+    let width = table_data.rows.first().map_or(0, Vec::len);
+    if width == 0 {
+        return Err(ExportError::EmptyTable);
+    }
+    let header_rows = table_data.header_rows.min(table_data.rows.len());
+    let (header, body) = table_data.rows.split_at(header_rows);
+
+    let mut output = String::new();
+
+    // 表头行
+    let header_cells: Vec<String> = (0..width).map(|col| header_text(header, col)).collect();
+    push_row(&mut output, header_cells.iter().map(String::as_str));
+
+    // 分隔行
+    let separators: Vec<&str> = (0..width)
+        .map(|col| match column_align(table_data, body, col, options) {
+            Some(ColumnAlign::Left) => ":---",
+            Some(ColumnAlign::Center) => ":---:",
+            Some(ColumnAlign::Right) => "---:",
+            None => "---",
+        })
+        .collect();
+    push_row(&mut output, separators.iter().copied());
+
+    // 数据行
+    for row in body {
+        let cells: Vec<String> = row.iter().map(|text| escape_cell(text)).collect();
+        push_row(&mut output, cells.iter().map(String::as_str));
+    }
+
+    if let Some(callback) = progress_callback {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(100.0));
+    }
+
+    Ok(output.into_bytes())
+}
+
+/// 写入一行 `| a | b |`
+fn push_row<'a>(output: &mut String, cells: impl Iterator<Item = &'a str>) {
+    output.push('|');
+    for cell in cells {
+        output.push(' ');
+        output.push_str(cell);
+        output.push_str(" |");
+    }
+    output.push('\n');
+}
+
+/// 合并多行表头中同一列的文本，跳过空白和与上一行重复的文本
+fn header_text(header: &[Vec<String>], col: usize) -> String {
+    let mut parts: Vec<String> = Vec::with_capacity(header.len());
+    for row in header {
+        let text = escape_cell(&row[col]);
+        if !text.is_empty() && parts.last() != Some(&text) {
+            parts.push(text);
+        }
+    }
+    parts.join(" / ")
+}
+
+/// 确定列的对齐方式
+///
+/// 优先使用 CSS `text-align`；未指定时，所有非空单元格都是数字或百分比的列右对齐
+fn column_align(
+    table_data: &TableData,
+    body: &[Vec<String>],
+    col: usize,
+    options: &ExportOptions,
+) -> Option<ColumnAlign> {
+    if let Some(align) = table_data.alignments.get(col).copied().flatten() {
+        return Some(align);
+    }
+
+    let cell_type = options.column_types.get(col).copied().unwrap_or_default();
+    let mut values = body
+        .iter()
+        .map(|row| row[col].as_str())
+        .filter(|text| !text.trim().is_empty())
+        .peekable();
+    let numeric = values.peek().is_some()
+        && values.all(|text| {
+            matches!(
+                resolve_cell_value(text, cell_type),
+                CellValue::Number { .. } | CellValue::Percent { .. }
+            )
+        });
+
+    numeric.then_some(ColumnAlign::Right)
+}
+
+/// 转义单元格文本：`|` 转义为 `\|`，换行转换为 `<br>`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn escape_cell(text: &str) -> String {
+    let text = text.trim();
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '|' => escaped.push_str("\\|"),
+            '\r' => {
+                // \r\n 视为一个换行
+                chars.next_if_eq(&'\n');
+                escaped.push_str("<br>");
+            }
+            '\n' => escaped.push_str("<br>"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,466 @@
+/// OpenDocument 电子表格（ODS）导出模块
+///
+/// ODS 文件是一个 zip 包：未压缩的 `mimetype` 必须是第一个条目，
+/// 其后是 `content.xml`（表格数据与单元格样式）、`styles.xml` 和 `META-INF/manifest.xml`
+use super::export_xlsx::cell_value;
+use super::options::ExportOptions;
+use super::table_extractor::{MergeSpans, TableData};
+use super::type_inference::{CellType, CellValue};
+use crate::error::ExportError;
+use crate::validation::validate_sheet_names;
+use std::fmt::Write as _;
+use std::io::{Cursor, Write};
+use wasm_bindgen::prelude::*;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// ODS 的 MIME 类型，同时写入 `mimetype` 条目
+const ODS_MIME_TYPE: &str = "application/vnd.oasis.opendocument.spreadsheet";
+
+/// ODF 版本
+const ODF_VERSION: &str = "1.3";
+
+/// content.xml 与 styles.xml 共用的命名空间声明
+const NAMESPACES: &str = concat!(
+    r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" "#,
+    r#"xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" "#,
+    r#"xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" "#,
+    r#"xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" "#,
+    r#"xmlns:number="urn:oasis:names:tc:opendocument:xmlns:datastyle:1.0" "#,
+    r#"xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0""#,
+);
+
+/// 生成 ODS 文件内容
+///
+/// # 参数
+/// * `table_data` - 表格数据（含合并区域）
+/// * `options` - 导出配置（使用其中的工作表名、列类型和进度回调）
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - ODS 文件字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn build_ods_bytes(
+    table_data: &TableData,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let sheet_name = options
+        .sheet_name
+        .clone()
+        .unwrap_or_else(|| "Sheet1".to_string());
+    build_ods_workbook_bytes(&[(sheet_name, table_data)], options)
+}
+
+/// 生成包含多个工作表的 ODS 文件内容
+///
+/// 与 Excel 工作簿相同：表头行写入为文本，其余单元格按列类型写入数字、百分比、布尔值或日期，
+/// 合并区域还原为跨行跨列的单元格
+///
+/// # 参数
+/// * `sheets` - 工作表名称与表格数据的列表，每项写入一个工作表
+/// * `options` - 导出配置（使用其中的列类型和进度回调，进度按所有工作表的总行数计算）
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - ODS 文件字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+pub fn build_ods_workbook_bytes(
+    sheets: &[(String, &TableData)],
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let names: Vec<&str> = sheets.iter().map(|(name, _)| name.as_str()).collect();
+    validate_sheet_names(&names)?;
+
+    let total_rows: usize = sheets.iter().map(|(_, data)| data.rows.len()).sum();
+    if total_rows == 0 {
+        return Err(ExportError::EmptyTable);
+    }
+    let progress_callback = options.progress_callback.as_ref();
+
+    // 报告初始进度
+    if let Some(callback) = progress_callback {
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(0.0));
+    }
+
+    let mut styles = CellStyles::default();
+    let mut body = String::new();
+    let mut written_rows = 0;
+
+    for (sheet_name, table_data) in sheets {
+        let width = table_data.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let spans = MergeSpans::new(table_data);
+
+        let _ = write!(
+            body,
+            r#"<table:table table:name="{}"><table:table-column table:number-columns-repeated="{}"/>"#,
+            escape_xml(sheet_name),
+            width.max(1)
+        );
+
+        let header_rows = table_data.header_rows.min(table_data.rows.len());
+        for row in 0..table_data.rows.len() {
+            if row == 0 && header_rows > 0 {
+                body.push_str("<table:table-header-rows>");
+            }
+
+            body.push_str("<table:table-row>");
+            for col in 0..table_data.rows[row].len() {
+                write_cell(
+                    &mut body,
+                    table_data,
+                    row,
+                    col,
+                    &spans,
+                    &options.column_types,
+                    &mut styles,
+                );
+            }
+            body.push_str("</table:table-row>");
+
+            if row + 1 == header_rows {
+                body.push_str("</table:table-header-rows>");
+            }
+
+            // 定期报告进度（每10行或最后一行）
+            written_rows += 1;
+            if let Some(callback) = progress_callback
+                && (written_rows % 10 == 1 || written_rows == total_rows)
+            {
+                let progress = (written_rows as f64 / total_rows as f64) * 100.0;
+                let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(progress));
+            }
+        }
+
+        body.push_str("</table:table>");
+    }
+
+    let content = format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<office:document-content {} office:version="{}">"#,
+            "<office:automatic-styles>{}</office:automatic-styles>",
+            "<office:body><office:spreadsheet>{}</office:spreadsheet></office:body>",
+            "</office:document-content>"
+        ),
+        NAMESPACES,
+        ODF_VERSION,
+        styles.to_xml(),
+        body
+    );
+
+    package(&content)
+}
+
+/// 将 content.xml 与固定的 styles.xml、manifest 打包为 ODS 文件
+fn package(content: &str) -> Result<Vec<u8>, ExportError> {
+    let styles = format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<office:document-styles {} office:version="{}"><office:styles/></office:document-styles>"#
+        ),
+        NAMESPACES, ODF_VERSION
+    );
+    let manifest = format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="{0}">"#,
+            r#"<manifest:file-entry manifest:full-path="/" manifest:version="{0}" manifest:media-type="{1}"/>"#,
+            r#"<manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>"#,
+            r#"<manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>"#,
+            "</manifest:manifest>"
+        ),
+        ODF_VERSION, ODS_MIME_TYPE
+    );
+
+    let stored = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let entries = [
+        ("mimetype", ODS_MIME_TYPE, stored),
+        ("content.xml", content, deflated),
+        ("styles.xml", styles.as_str(), deflated),
+        ("META-INF/manifest.xml", manifest.as_str(), deflated),
+    ];
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data, file_options) in entries {
+        zip.start_file(name, file_options)
+            .map_err(|e| ods_write_failed(e.to_string()))?;
+        zip.write_all(data.as_bytes())
+            .map_err(|e| ods_write_failed(e.to_string()))?;
+    }
+    let cursor = zip.finish().map_err(|e| ods_write_failed(e.to_string()))?;
+
+    Ok(cursor.into_inner())
+}
+
+/// 将 zip 写入错误转换为导出错误
+fn ods_write_failed(message: String) -> ExportError {
+    ExportError::OdsWriteFailed { message }
+}
+
+/// 写入一个单元格
+///
+/// 合并区域的左上角单元格带有跨度属性，被覆盖的位置写入 `covered-table-cell`
+fn write_cell(
+    body: &mut String,
+    table_data: &TableData,
+    row: usize,
+    col: usize,
+    spans: &MergeSpans,
+    column_types: &[CellType],
+    styles: &mut CellStyles,
+) {
+    if spans.covered.contains(&(row, col)) {
+        body.push_str("<table:covered-table-cell/>");
+        return;
+    }
+
+    let text = &table_data.rows[row][col];
+    let value = cell_value(table_data, row, col, column_types);
+    let span = spans.anchors.get(&(row, col)).copied();
+
+    body.push_str("<table:table-cell");
+    if let Some(style) = styles.name(&value, span.is_some()) {
+        let _ = write!(body, r#" table:style-name="{}""#, style);
+    }
+    if let Some((rows, cols)) = span {
+        let _ = write!(
+            body,
+            r#" table:number-rows-spanned="{}" table:number-columns-spanned="{}""#,
+            rows, cols
+        );
+    }
+    // 空单元格不写入值类型
+    if text.is_empty() && value == CellValue::Text {
+        body.push_str("/>");
+        return;
+    }
+    write_value_attributes(body, &value);
+    body.push('>');
+    for line in text.lines() {
+        let _ = write!(body, "<text:p>{}</text:p>", paragraph_xml(line));
+    }
+    body.push_str("</table:table-cell>");
+}
+
+/// 写入单元格的值类型和值属性
+fn write_value_attributes(body: &mut String, value: &CellValue) {
+    let _ = match *value {
+        CellValue::Number { value, .. } if value.is_finite() => write!(
+            body,
+            r#" office:value-type="float" office:value="{}""#,
+            value
+        ),
+        CellValue::Percent { value, .. } if value.is_finite() => write!(
+            body,
+            r#" office:value-type="percentage" office:value="{}""#,
+            value
+        ),
+        CellValue::Boolean(value) => write!(
+            body,
+            r#" office:value-type="boolean" office:boolean-value="{}""#,
+            value
+        ),
+        CellValue::Date { year, month, day } => write!(
+            body,
+            r#" office:value-type="date" office:date-value="{:04}-{:02}-{:02}""#,
+            year, month, day
+        ),
+        CellValue::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        } => write!(
+            body,
+            r#" office:value-type="date" office:date-value="{:04}-{:02}-{:02}T{:02}:{:02}:{}""#,
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            format_seconds(second)
+        ),
+        _ => write!(body, r#" office:value-type="string""#),
+    };
+}
+
+/// 秒数格式化为 `ss` 或 `ss.fff`
+fn format_seconds(second: f64) -> String {
+    if second.fract() == 0.0 {
+        format!("{:02}", second as u32)
+    } else {
+        format!("{:06.3}", second)
+    }
+}
+
+/// 单元格样式表
+///
+/// 按数字格式和是否为合并单元格生成自动样式，相同的组合共用一个样式
+#[derive(Default)]
+struct CellStyles {
+    /// (数字格式, 数据样式 XML)，名称为 `N<序号>`
+    data_styles: Vec<(String, String)>,
+    /// (数据样式序号, 是否合并) -> 单元格样式名称
+    cell_styles: Vec<((Option<usize>, bool), String)>,
+}
+
+impl CellStyles {
+    /// 获取单元格值对应的样式名称，常规文本且未合并时返回 `None`
+    fn name(&mut self, value: &CellValue, merged: bool) -> Option<String> {
+        let data_style = value.num_format().map(|key| self.data_style(key, value));
+        if data_style.is_none() && !merged {
+            return None;
+        }
+
+        let key = (data_style, merged);
+        if let Some((_, name)) = self.cell_styles.iter().find(|(k, _)| *k == key) {
+            return Some(name.clone());
+        }
+        let name = format!("ce{}", self.cell_styles.len() + 1);
+        self.cell_styles.push((key, name.clone()));
+        Some(name)
+    }
+
+    /// 获取数字格式对应的数据样式序号
+    fn data_style(&mut self, key: String, value: &CellValue) -> usize {
+        if let Some(index) = self.data_styles.iter().position(|(k, _)| *k == key) {
+            return index;
+        }
+        let name = format!("N{}", self.data_styles.len() + 1);
+        self.data_styles.push((key, data_style_xml(value, &name)));
+        self.data_styles.len() - 1
+    }
+
+    /// 生成 `office:automatic-styles` 的内容
+    fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        for (_, data_style) in &self.data_styles {
+            xml.push_str(data_style);
+        }
+        for ((data_style, merged), name) in &self.cell_styles {
+            let _ = write!(
+                xml,
+                r#"<style:style style:name="{}" style:family="table-cell""#,
+                name
+            );
+            if let Some(index) = data_style {
+                let _ = write!(xml, r#" style:data-style-name="N{}""#, index + 1);
+            }
+            xml.push('>');
+            // 与 Excel 导出一致，合并单元格水平和垂直居中
+            if *merged {
+                xml.push_str(concat!(
+                    r#"<style:table-cell-properties style:vertical-align="middle"/>"#,
+                    r#"<style:paragraph-properties fo:text-align="center"/>"#
+                ));
+            }
+            xml.push_str("</style:style>");
+        }
+        xml
+    }
+}
+
+/// 生成数据样式的 XML
+fn data_style_xml(value: &CellValue, name: &str) -> String {
+    const DATE: &str = concat!(
+        r#"<number:year number:style="long"/><number:text>-</number:text>"#,
+        r#"<number:month number:style="long"/><number:text>-</number:text>"#,
+        r#"<number:day number:style="long"/>"#
+    );
+    const TIME: &str = concat!(
+        r#"<number:text> </number:text><number:hours number:style="long"/><number:text>:</number:text>"#,
+        r#"<number:minutes number:style="long"/><number:text>:</number:text>"#,
+        r#"<number:seconds number:style="long"/>"#
+    );
+
+    match *value {
+        CellValue::Number {
+            decimals, grouped, ..
+        } => format!(
+            r#"<number:number-style style:name="{0}"><number:number number:decimal-places="{1}" number:min-decimal-places="{1}" number:min-integer-digits="1" number:grouping="{2}"/></number:number-style>"#,
+            name, decimals, grouped
+        ),
+        CellValue::Percent { decimals, .. } => format!(
+            r#"<number:percentage-style style:name="{0}"><number:number number:decimal-places="{1}" number:min-decimal-places="{1}" number:min-integer-digits="1"/><number:text>%</number:text></number:percentage-style>"#,
+            name, decimals
+        ),
+        CellValue::Date { .. } => format!(
+            r#"<number:date-style style:name="{}">{}</number:date-style>"#,
+            name, DATE
+        ),
+        CellValue::DateTime { .. } => format!(
+            r#"<number:date-style style:name="{}">{}{}</number:date-style>"#,
+            name, DATE, TIME
+        ),
+        CellValue::Text | CellValue::Boolean(_) => String::new(),
+    }
+}
+
+/// 转义 XML 特殊字符，并去除 XML 1.0 不允许的控制字符
+/// 生成 `<text:p>` 段落的内容
+///
+/// ODF 会把连续的空白合并为一个空格，并忽略段落开头的空白，因此除夹在两个普通字符之间的单个空格外，
+/// 空格都写为 `<text:s text:c="n"/>`，制表符写为 `<text:tab/>`
+fn paragraph_xml(line: &str) -> String {
+    let bytes = line.as_bytes();
+    let mut xml = String::with_capacity(line.len());
+    let mut text_start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\t' => {
+                xml.push_str(&escape_xml(&line[text_start..i]));
+                xml.push_str("<text:tab/>");
+                i += 1;
+                text_start = i;
+            }
+            b' ' => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|&b| b != b' ')
+                    .map_or(bytes.len(), |offset| i + offset);
+                let count = end - i;
+                let between_text = count == 1
+                    && i > 0
+                    && end < bytes.len()
+                    && bytes[i - 1] != b'\t'
+                    && bytes[end] != b'\t';
+                if !between_text {
+                    xml.push_str(&escape_xml(&line[text_start..i]));
+                    if count == 1 {
+                        xml.push_str("<text:s/>");
+                    } else {
+                        let _ = write!(xml, r#"<text:s text:c="{}"/>"#, count);
+                    }
+                    text_start = end;
+                }
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    xml.push_str(&escape_xml(&line[text_start..]));
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' => escaped.push('\t'),
+            ch if (ch as u32) < 0x20 || ch == '\u{FFFE}' || ch == '\u{FFFF}' => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
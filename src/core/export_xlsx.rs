@@ -1,114 +1,434 @@
 /// Excel XLSX 导出模块
 ///
 /// 提供 Excel XLSX 格式的表格导出功能
-use crate::resource::UrlGuard;
-use crate::validation::{ensure_extension, validate_filename};
-use rust_xlsxwriter::Workbook;
+use super::options::ExportOptions;
+use super::sanitize::{FormulaSanitization, is_formula_like};
+use super::table_extractor::TableData;
+use super::type_inference::{CellType, CellValue, resolve_cell_value};
+use crate::error::ExportError;
+use crate::i18n::Message;
+use crate::validation::{validate_sheet_name, validate_sheet_names};
+use rust_xlsxwriter::{ExcelDateTime, Format, FormatAlign, Url, Workbook, Worksheet, XlsxError};
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
-use web_sys::{Blob, HtmlAnchorElement, Url};
 
-/// 导出为 Excel XLSX 格式
+/// Excel 每个工作表最多支持的超链接数，超出时 Excel 会提示修复文件
+const MAX_SHEET_LINKS: usize = 65_530;
+
+/// 生成 Excel XLSX 文件内容
 ///
 /// # 参数
-/// * `table_data` - 表格数据（二维字符串数组）
-/// * `filename` - 可选的导出文件名
-/// * `progress_callback` - 可选的进度回调函数
+/// * `table_data` - 表格数据（含合并区域）
+/// * `options` - 导出配置（使用其中的工作表名、列类型和进度回调）
 ///
 /// # 返回值
-/// * `Ok(())` - 导出成功
-/// * `Err(JsValue)` - 导出失败，包含错误信息
-pub fn export_as_xlsx(
-    table_data: Vec<Vec<String>>,
-    filename: Option<String>,
-    progress_callback: Option<js_sys::Function>,
-) -> Result<(), JsValue> {
-    let total_rows = table_data.len();
+/// * `Ok(Vec<u8>)` - XLSX 文件字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn build_xlsx_bytes(
+    table_data: &TableData,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let sheet_name = options
+        .sheet_name
+        .clone()
+        .unwrap_or_else(|| "Sheet1".to_string());
+    build_workbook_bytes(&[(sheet_name, table_data)], options)
+}
+
+/// 生成包含多个工作表的 Excel XLSX 文件内容
+///
+/// # 参数
+/// * `sheets` - 工作表名称与表格数据的列表，每项写入一个工作表
+/// * `options` - 导出配置（使用其中的列类型和进度回调，进度按所有工作表的总行数计算）
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - XLSX 文件字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+pub fn build_workbook_bytes(
+    sheets: &[(String, &TableData)],
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let names: Vec<&str> = sheets.iter().map(|(name, _)| name.as_str()).collect();
+    validate_sheet_names(&names)?;
+
+    let total_rows: usize = sheets.iter().map(|(_, data)| data.rows.len()).sum();
+    let progress_callback = options.progress_callback.as_ref();
 
     // 报告初始进度
-    if let Some(ref callback) = progress_callback {
+    if let Some(callback) = progress_callback {
         let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(0.0));
     }
 
-    // 创建工作簿与工作表
-    let mut workbook = Workbook::new();
-    let worksheet = workbook.add_worksheet();
-
-    // 写入所有数据，并报告进度
-    for (i, row_data) in table_data.iter().enumerate() {
-        for (j, cell_text) in row_data.iter().enumerate() {
-            worksheet
-                .write_string(i as u32, j as u16, cell_text)
-                .map_err(|e| JsValue::from_str(&format!("写入 Excel 单元格失败: {}", e)))?;
-        }
+    let mut worksheets = Vec::with_capacity(sheets.len());
+    let mut written_rows = 0;
 
-        // 定期报告进度（每10行或最后一行）
-        if let Some(ref callback) = progress_callback
-            && (i % 10 == 0 || i == total_rows - 1) {
-                let progress = ((i + 1) as f64 / total_rows as f64) * 100.0;
+    for (sheet_name, table_data) in sheets {
+        let mut writer = XlsxSheetWriter::new(sheet_name, options)?;
+
+        // 写入所有数据，并报告进度
+        for i in 0..table_data.rows.len() {
+            writer.write_row(table_data, i)?;
+
+            // 定期报告进度（每10行或最后一行）
+            written_rows += 1;
+            if let Some(callback) = progress_callback
+                && (written_rows % 10 == 1 || written_rows == total_rows)
+            {
+                let progress = (written_rows as f64 / total_rows as f64) * 100.0;
                 let _ = callback.call1(&JsValue::NULL, &JsValue::from_f64(progress));
             }
+        }
+
+        worksheets.push(writer.finish(table_data)?);
+    }
+
+    save_workbook(worksheets)
+}
+
+/// 逐行写入的 Excel 工作表
+///
+/// 一次性导出和分批导出共用，分批导出可以在两次 `write_row` 之间让出控制权
+pub struct XlsxSheetWriter {
+    worksheet: Worksheet,
+    formats: FormatCache,
+    link_formats: FormatCache,
+    column_types: Vec<CellType>,
+    quote_prefix: bool,
+    /// 已写入的超链接数
+    link_count: usize,
+    /// 超出超链接数量限制后第一个按普通文本写入的链接单元格，之后的链接单元格均按普通文本写入
+    first_skipped_link: Option<(usize, usize)>,
+}
+
+impl XlsxSheetWriter {
+    /// 创建指定名称的工作表
+    ///
+    /// # 参数
+    /// * `sheet_name` - 工作表名称
+    /// * `options` - 导出配置（使用其中的列类型和公式注入防护方式）
+    pub fn new(sheet_name: &str, options: &ExportOptions) -> Result<Self, ExportError> {
+        validate_sheet_name(sheet_name)?;
+
+        let mut worksheet = Worksheet::new();
+        worksheet
+            .set_name(sheet_name)
+            .map_err(|e| ExportError::InvalidSheetName {
+                sheet_name: sheet_name.to_string(),
+                reason: Message::Detail(e.to_string()),
+            })?;
+
+        // Prefix 通过单元格的引号前缀属性实现，Excel 不会把这些单元格当作公式，文本中也不会出现 `'`
+        let quote_prefix = options.resolved_sanitization() == FormulaSanitization::Prefix;
+
+        Ok(Self {
+            worksheet,
+            formats: FormatCache::new(Format::new(), quote_prefix),
+            link_formats: FormatCache::new(Format::new().set_hyperlink(), quote_prefix),
+            column_types: options.column_types.clone(),
+            quote_prefix,
+            link_count: 0,
+            first_skipped_link: None,
+        })
+    }
+
+    /// 写入表格数据中的一行
+    ///
+    /// # 参数
+    /// * `table_data` - 表格数据
+    /// * `row` - 行索引
+    pub fn write_row(&mut self, table_data: &TableData, row: usize) -> Result<(), ExportError> {
+        for (col, cell_text) in table_data.rows[row].iter().enumerate() {
+            if let Some(url) = table_data.links.get(&(row, col)) {
+                if self.link_count == MAX_SHEET_LINKS {
+                    self.first_skipped_link.get_or_insert((row, col));
+                } else if write_link(
+                    &mut self.worksheet,
+                    row as u32,
+                    col as u16,
+                    url,
+                    cell_text,
+                    self.link_formats.text(cell_text),
+                )
+                .is_ok()
+                {
+                    self.link_count += 1;
+                    continue;
+                }
+            }
+
+            let value = cell_value(table_data, row, col, &self.column_types);
+            write_typed_cell(
+                &mut self.worksheet,
+                row as u32,
+                col as u16,
+                cell_text,
+                value,
+                &mut self.formats,
+            )
+            .map_err(|e| xlsx_write_failed(Some(row), Some(col), e))?;
+        }
+        Ok(())
+    }
+
+    /// 写入合并区域并返回完成的工作表
+    ///
+    /// # 参数
+    /// * `table_data` - 表格数据（含合并区域）
+    pub fn finish(mut self, table_data: &TableData) -> Result<Worksheet, ExportError> {
+        // 还原 HTML 中的 colspan/rowspan
+        write_merges(
+            &mut self.worksheet,
+            table_data,
+            &self.column_types,
+            self.quote_prefix,
+            self.first_skipped_link,
+        )?;
+        Ok(self.worksheet)
+    }
+}
+
+/// 将工作表组装为工作簿并生成 XLSX 文件内容
+///
+/// # 参数
+/// * `worksheets` - 按顺序排列的工作表
+///
+/// # 返回值
+/// * `Ok(Vec<u8>)` - XLSX 文件字节
+/// * `Err(ExportError)` - 生成失败，包含错误码和上下文
+pub fn save_workbook(worksheets: Vec<Worksheet>) -> Result<Vec<u8>, ExportError> {
+    let mut workbook = Workbook::new();
+    for worksheet in worksheets {
+        workbook.push_worksheet(worksheet);
     }
 
     // 将工作簿写入内存缓冲区
     let xlsx_bytes = workbook
         .save_to_buffer()
-        .map_err(|e| JsValue::from_str(&format!("生成 Excel 文件失败: {}", e)))?;
+        .map_err(|e| xlsx_write_failed(None, None, e))?;
 
     if xlsx_bytes.is_empty() {
-        return Err(JsValue::from_str("没有可导出的数据"));
+        return Err(ExportError::EmptyTable);
     }
 
-    // 创建并下载文件
-    create_and_download_xlsx(&xlsx_bytes, filename)
+    Ok(xlsx_bytes)
 }
 
-/// 创建 Excel Blob 并触发下载
+/// 将表格中的合并区域写入工作表
+///
+/// 合并区域的值取自左上角单元格，其余位置由 `merge_range` 填充为空白，
+/// 因此在 Repeat 模式下重复写入的值也会被覆盖
 ///
 /// # 参数
-/// * `data` - Excel 文件数据字节
-/// * `filename` - 可选的导出文件名
-fn create_and_download_xlsx(data: &[u8], filename: Option<String>) -> Result<(), JsValue> {
-    let window = web_sys::window().ok_or_else(|| JsValue::from_str("无法获取 window 对象"))?;
-    let document = window
-        .document()
-        .ok_or_else(|| JsValue::from_str("无法获取 document 对象"))?;
-
-    // 创建 Excel Blob 对象
-    let blob_property_bag = web_sys::BlobPropertyBag::new();
-    blob_property_bag.set_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet");
-
-    let array = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
-    let blob = Blob::new_with_u8_array_sequence_and_options(&array, &blob_property_bag)
-        .map_err(|e| JsValue::from_str(&format!("创建 Blob 对象失败: {:?}", e)))?;
-
-    // 创建下载链接
-    let url = Url::create_object_url_with_blob(&blob)
-        .map_err(|e| JsValue::from_str(&format!("创建下载链接失败: {:?}", e)))?;
-
-    // 使用 RAII 模式确保 URL 资源释放
-    let _url_guard = UrlGuard::new(&url);
-
-    // 设置文件名
-    let final_filename = filename.unwrap_or_else(|| "table_export.xlsx".to_string());
-
-    // 验证文件名安全性
-    if let Err(e) = validate_filename(&final_filename) {
-        return Err(JsValue::from_str(&format!("文件名验证失败: {}", e)));
+/// * `worksheet` - 目标工作表
+/// * `table_data` - 表格数据（含合并区域）
+/// * `column_types` - 按列覆盖的单元格类型
+/// * `quote_prefix` - 是否为类似公式的文本设置引号前缀
+/// * `first_skipped_link` - 超出超链接数量限制后第一个按普通文本写入的链接单元格
+fn write_merges(
+    worksheet: &mut Worksheet,
+    table_data: &TableData,
+    column_types: &[CellType],
+    quote_prefix: bool,
+    first_skipped_link: Option<(usize, usize)>,
+) -> Result<(), ExportError> {
+    if table_data.merges.is_empty() {
+        return Ok(());
     }
 
-    let final_filename = ensure_extension(&final_filename, "xlsx");
+    // 与浏览器中表头单元格的默认样式保持一致
+    let merge_format = Format::new()
+        .set_align(FormatAlign::Center)
+        .set_align(FormatAlign::VerticalCenter);
+    let mut formats = FormatCache::new(merge_format.clone(), quote_prefix);
+    let link_formats = FormatCache::new(merge_format.set_hyperlink(), quote_prefix);
 
-    // 创建下载链接元素
-    let anchor = document
-        .create_element("a")
-        .map_err(|e| JsValue::from_str(&format!("创建下载链接元素失败: {:?}", e)))?;
-    let anchor = anchor
-        .dyn_into::<HtmlAnchorElement>()
-        .map_err(|_| JsValue::from_str("创建的元素不是有效的锚点元素"))?;
+    for merge in &table_data.merges {
+        let text = table_data
+            .rows
+            .get(merge.first_row)
+            .and_then(|row| row.get(merge.first_col))
+            .map(String::as_str)
+            .unwrap_or("");
 
-    anchor.set_href(&url);
-    anchor.set_download(&final_filename);
-    anchor.click();
+        worksheet
+            .merge_range(
+                merge.first_row as u32,
+                merge.first_col as u16,
+                merge.last_row as u32,
+                merge.last_col as u16,
+                text,
+                formats.text(text),
+            )
+            .map_err(|e| xlsx_write_failed(Some(merge.first_row), Some(merge.first_col), e))?;
 
+        // merge_range 只能写入字符串，超链接和带类型的值需要在左上角单元格重新写入；
+        // 超链接按行顺序计数，位于第一个超出限制的链接之后的单元格按普通文本写入
+        let anchor = (merge.first_row, merge.first_col);
+        if let Some(url) = table_data.links.get(&anchor)
+            && first_skipped_link.is_none_or(|skipped| anchor < skipped)
+            && write_link(
+                worksheet,
+                merge.first_row as u32,
+                merge.first_col as u16,
+                url,
+                text,
+                link_formats.text(text),
+            )
+            .is_ok()
+        {
+            continue;
+        }
+
+        let value = cell_value(table_data, merge.first_row, merge.first_col, column_types);
+        if value != CellValue::Text {
+            write_typed_cell(
+                worksheet,
+                merge.first_row as u32,
+                merge.first_col as u16,
+                text,
+                value,
+                &mut formats,
+            )
+            .map_err(|e| xlsx_write_failed(Some(merge.first_row), Some(merge.first_col), e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 写入超链接单元格
+///
+/// 链接超出 Excel 的长度限制等无法写入的情况返回错误，调用方改为写入普通单元格
+///
+/// # 参数
+/// * `worksheet` - 目标工作表
+/// * `row` / `col` - 单元格位置
+/// * `url` - 链接地址
+/// * `text` - 显示的文本，为空时显示链接地址
+/// * `format` - 单元格格式（需包含超链接样式）
+fn write_link(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    url: &str,
+    text: &str,
+    format: &Format,
+) -> Result<(), XlsxError> {
+    worksheet.write_url_with_format(row, col, Url::new(url).set_text(text), format)?;
+    Ok(())
+}
+
+/// 将 rust_xlsxwriter 的错误转换为导出错误
+fn xlsx_write_failed(row: Option<usize>, column: Option<usize>, error: XlsxError) -> ExportError {
+    ExportError::XlsxWriteFailed {
+        row,
+        column,
+        message: error.to_string(),
+    }
+}
+
+/// 解析指定位置单元格的值
+///
+/// 表头行始终为文本，其余行按单元格或列的类型覆盖解析，均未指定时自动推断
+pub(super) fn cell_value(
+    table_data: &TableData,
+    row: usize,
+    col: usize,
+    column_types: &[CellType],
+) -> CellValue {
+    if row < table_data.header_rows {
+        return CellValue::Text;
+    }
+    let cell_type = table_data.cell_type(row, col, column_types);
+    resolve_cell_value(&table_data.rows[row][col], cell_type)
+}
+
+/// 按数字格式缓存的单元格格式
+///
+/// 避免为每个单元格重复创建相同的 `Format`
+struct FormatCache {
+    base: Format,
+    /// 类似公式的文本使用的带引号前缀的格式，未使用 Prefix 防护时为 `None`
+    quoted: Option<Format>,
+    by_num_format: HashMap<String, Format>,
+}
+
+impl FormatCache {
+    fn new(base: Format, quote_prefix: bool) -> Self {
+        Self {
+            quoted: quote_prefix.then(|| base.clone().set_quote_prefix()),
+            base,
+            by_num_format: HashMap::new(),
+        }
+    }
+
+    /// 文本单元格使用的格式
+    fn text(&self, text: &str) -> &Format {
+        match &self.quoted {
+            Some(quoted) if is_formula_like(text) => quoted,
+            _ => &self.base,
+        }
+    }
+
+    fn get(&mut self, num_format: Option<String>) -> &Format {
+        match num_format {
+            Some(num_format) => self
+                .by_num_format
+                .entry(num_format)
+                .or_insert_with_key(|key| self.base.clone().set_num_format(key)),
+            None => &self.base,
+        }
+    }
+}
+
+/// 按推断出的类型写入单元格
+///
+/// # 参数
+/// * `worksheet` - 目标工作表
+/// * `row` / `col` - 单元格位置
+/// * `text` - 单元格原始文本（类型为文本时写入）
+/// * `value` - 解析后的单元格值
+/// * `formats` - 格式缓存
+fn write_typed_cell(
+    worksheet: &mut Worksheet,
+    row: u32,
+    col: u16,
+    text: &str,
+    value: CellValue,
+    formats: &mut FormatCache,
+) -> Result<(), XlsxError> {
+    let format = match value {
+        CellValue::Text => formats.text(text),
+        _ => formats.get(value.num_format()),
+    };
+    match value {
+        CellValue::Text => worksheet.write_string_with_format(row, col, text, format)?,
+        CellValue::Number { value, .. } | CellValue::Percent { value, .. } => {
+            worksheet.write_number_with_format(row, col, value, format)?
+        }
+        CellValue::Boolean(value) => {
+            worksheet.write_boolean_with_format(row, col, value, format)?
+        }
+        CellValue::Date { year, month, day } => {
+            let datetime = ExcelDateTime::from_ymd(year, month, day)?;
+            worksheet.write_datetime_with_format(row, col, &datetime, format)?
+        }
+        CellValue::DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+        } => {
+            let datetime =
+                ExcelDateTime::from_ymd(year, month, day)?.and_hms(hour, minute, second)?;
+            worksheet.write_datetime_with_format(row, col, &datetime, format)?
+        }
+    };
     Ok(())
 }
@@ -0,0 +1,123 @@
+/// 导出文件输出模块
+///
+/// 提供文件名处理、Blob/File 创建以及触发浏览器下载的功能，供各导出格式共用
+use crate::error::ExportError;
+use crate::i18n::{Locale, Message};
+use crate::resource::UrlGuard;
+use crate::validation::{ensure_extension, validate_filename};
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, File, FilePropertyBag, HtmlAnchorElement, Url};
+
+/// 确定最终的导出文件名
+///
+/// 未提供文件名时使用对应语言的默认文件名（如 `table_export.<extension>`），
+/// 并验证文件名安全性、补齐扩展名
+///
+/// # 参数
+/// * `filename` - 可选的导出文件名
+/// * `extension` - 期望的扩展名（如 "csv"）
+/// * `locale` - 消息语言，决定默认文件名
+///
+/// # 返回值
+/// * `Ok(String)` - 验证通过并带有扩展名的文件名
+/// * `Err(ExportError)` - 文件名不合法
+pub fn resolve_filename(
+    filename: Option<String>,
+    extension: &str,
+    locale: Locale,
+) -> Result<String, ExportError> {
+    let final_filename =
+        filename.unwrap_or_else(|| format!("{}.{}", locale.default_file_stem(), extension));
+
+    // 验证文件名安全性
+    validate_filename(&final_filename)?;
+
+    Ok(ensure_extension(&final_filename, extension))
+}
+
+/// 创建 Blob 对象
+///
+/// # 参数
+/// * `data` - 文件数据字节
+/// * `mime_type` - MIME 类型
+pub fn create_blob(data: &[u8], mime_type: &str) -> Result<Blob, ExportError> {
+    let blob_property_bag = web_sys::BlobPropertyBag::new();
+    blob_property_bag.set_type(mime_type);
+
+    let array = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+    Blob::new_with_u8_array_sequence_and_options(&array, &blob_property_bag).map_err(|e| {
+        ExportError::BlobCreationFailed {
+            message: Message::BlobFailed {
+                detail: format!("{:?}", e),
+            },
+        }
+    })
+}
+
+/// 创建带文件名的 File 对象（File 继承自 Blob）
+///
+/// # 参数
+/// * `data` - 文件数据字节
+/// * `mime_type` - MIME 类型
+/// * `filename` - 已验证的文件名
+pub fn create_file(data: &[u8], mime_type: &str, filename: &str) -> Result<File, ExportError> {
+    let file_property_bag = FilePropertyBag::new();
+    file_property_bag.set_type(mime_type);
+
+    let array = js_sys::Array::of1(&js_sys::Uint8Array::from(data));
+    File::new_with_u8_array_sequence_and_options(&array, filename, &file_property_bag).map_err(
+        |e| ExportError::BlobCreationFailed {
+            message: Message::FileFailed {
+                detail: format!("{:?}", e),
+            },
+        },
+    )
+}
+
+/// 创建 Blob 并触发浏览器下载
+///
+/// # 参数
+/// * `data` - 文件数据字节
+/// * `mime_type` - MIME 类型
+/// * `filename` - 已验证的文件名
+pub fn download_bytes(data: &[u8], mime_type: &str, filename: &str) -> Result<(), ExportError> {
+    let document = get_document()?;
+
+    let blob = create_blob(data, mime_type)?;
+
+    // 创建下载链接
+    let url =
+        Url::create_object_url_with_blob(&blob).map_err(|e| ExportError::BlobCreationFailed {
+            message: Message::ObjectUrlFailed {
+                detail: format!("{:?}", e),
+            },
+        })?;
+
+    // 使用 RAII 模式确保 URL 资源释放
+    let _url_guard = UrlGuard::new(&url);
+
+    // 创建下载链接元素
+    let anchor = document.create_element("a").map_err(|e| {
+        ExportError::browser_api(Message::AnchorCreationFailed {
+            detail: format!("{:?}", e),
+        })
+    })?;
+    let anchor = anchor
+        .dyn_into::<HtmlAnchorElement>()
+        .map_err(|_| ExportError::browser_api(Message::AnchorInvalid))?;
+
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    Ok(())
+}
+
+/// 安全地获取全局的 document 对象
+pub fn get_document() -> Result<web_sys::Document, ExportError> {
+    let window =
+        web_sys::window().ok_or_else(|| ExportError::browser_api(Message::WindowUnavailable))?;
+    window
+        .document()
+        .ok_or_else(|| ExportError::browser_api(Message::DocumentUnavailable))
+}
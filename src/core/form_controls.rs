@@ -0,0 +1,117 @@
+/// 表单控件模块
+///
+/// 可编辑表格的单元格中常包含 `<input>`、`<select>`、`<textarea>`，
+/// 它们的 `innerText` 为空或是全部选项，需要读取控件的当前值
+use super::type_inference::CellType;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Element, HtmlElement, HtmlInputElement, HtmlOptionElement, HtmlSelectElement,
+    HtmlTextAreaElement,
+};
+
+/// 不导出值的 `<input>` 类型：按钮类控件没有用户输入，密码和文件路径不应写入导出文件
+const SKIPPED_INPUT_TYPES: &[&str] = &[
+    "hidden", "button", "submit", "reset", "image", "file", "password",
+];
+
+/// 单元格中一个表单控件的当前值
+///
+/// # 注意
+/// 这个类型主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormValue {
+    /// 输入框、文本域的值，或下拉框选中项的文本
+    Text(String),
+    /// 复选框的勾选状态
+    Checked(bool),
+}
+
+/// 合并单元格中各表单控件的值
+///
+/// # 参数
+/// * `values` - 单元格中按文档顺序排列的控件值
+///
+/// # 返回值
+/// * 只有一个复选框时为 `TRUE`/`FALSE`，并指定为布尔类型
+/// * 其他情况下非空的值以 `, ` 连接，类型由列类型或自动推断决定
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn form_cell_value(values: &[FormValue]) -> (String, Option<CellType>) {
+    let text = |value: &FormValue| match value {
+        FormValue::Text(text) => text.clone(),
+        FormValue::Checked(checked) => if *checked { "TRUE" } else { "FALSE" }.to_string(),
+    };
+
+    match values {
+        [value @ FormValue::Checked(_)] => (text(value), Some(CellType::Boolean)),
+        _ => (
+            values
+                .iter()
+                .map(text)
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join(", "),
+            None,
+        ),
+    }
+}
+
+/// 读取单元格的显示值和类型
+///
+/// 单元格包含表单控件时使用控件的当前值，否则使用 `innerText`
+pub(crate) fn cell_text(cell: &HtmlElement) -> (String, Option<CellType>) {
+    match read_form_values(cell) {
+        Some(values) => form_cell_value(&values),
+        None => (cell.inner_text(), None),
+    }
+}
+
+/// 读取单元格中所有表单控件的当前值，没有需要导出的控件时返回 `None`
+///
+/// 未选中的单选框不产生值，但仍表示单元格由控件组成
+fn read_form_values(cell: &Element) -> Option<Vec<FormValue>> {
+    // 大部分单元格只有文本，没有子元素时不必查询
+    if cell.child_element_count() == 0 {
+        return None;
+    }
+    let controls = cell.query_selector_all("input, select, textarea").ok()?;
+    let mut found = false;
+    let mut values = Vec::new();
+
+    for i in 0..controls.length() {
+        let Some(control) = controls.get(i) else {
+            continue;
+        };
+
+        if let Some(input) = control.dyn_ref::<HtmlInputElement>() {
+            let input_type = input.type_().to_ascii_lowercase();
+            if SKIPPED_INPUT_TYPES.contains(&input_type.as_str()) {
+                continue;
+            }
+            found = true;
+            match input_type.as_str() {
+                "checkbox" => values.push(FormValue::Checked(input.checked())),
+                "radio" if input.checked() => values.push(FormValue::Text(input.value())),
+                "radio" => {}
+                _ => values.push(FormValue::Text(input.value())),
+            }
+        } else if let Some(select) = control.dyn_ref::<HtmlSelectElement>() {
+            found = true;
+            let selected = select.selected_options();
+            let texts: Vec<String> = (0..selected.length())
+                .filter_map(|j| selected.item(j))
+                .filter_map(|option| option.dyn_into::<HtmlOptionElement>().ok())
+                .map(|option| option.text())
+                .collect();
+            values.push(FormValue::Text(texts.join(", ")));
+        } else if let Some(textarea) = control.dyn_ref::<HtmlTextAreaElement>() {
+            found = true;
+            values.push(FormValue::Text(textarea.value()));
+        }
+    }
+
+    found.then_some(values)
+}
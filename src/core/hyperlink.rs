@@ -0,0 +1,73 @@
+/// 超链接模块
+///
+/// 读取单元格中 `<a href>` 的链接地址，XLSX 将其写为真正的超链接，
+/// CSV/TSV 按 `link_format` 输出链接文本、地址或两者
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{Element, HtmlAnchorElement};
+
+/// 可以写入导出文件的链接协议，`javascript:` 等其他协议的链接被忽略
+const EXPORTABLE_SCHEMES: &[&str] = &["http://", "https://", "ftp://", "ftps://", "mailto:"];
+
+/// CSV/TSV 中带链接单元格的输出方式
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LinkFormat {
+    /// 只输出链接文本（默认）
+    #[default]
+    Text,
+    /// 只输出链接地址
+    Url,
+    /// 输出 `文本 (地址)`，文本为空或与地址相同时只输出地址
+    Both,
+}
+
+/// 链接地址是否可以写入导出文件
+///
+/// 只接受 `http`、`https`、`ftp`、`ftps` 和 `mailto` 协议（不区分大小写）
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn is_exportable_url(url: &str) -> bool {
+    EXPORTABLE_SCHEMES.iter().any(|scheme| {
+        url.get(..scheme.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    })
+}
+
+/// 按输出方式生成带链接单元格的文本
+///
+/// # 参数
+/// * `text` - 单元格文本
+/// * `url` - 链接地址
+/// * `format` - 输出方式
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn link_text(text: &str, url: &str, format: LinkFormat) -> String {
+    match format {
+        LinkFormat::Text => text.to_string(),
+        LinkFormat::Url => url.to_string(),
+        LinkFormat::Both if text.trim().is_empty() || text.trim() == url => url.to_string(),
+        LinkFormat::Both => format!("{} ({})", text, url),
+    }
+}
+
+/// 读取单元格中第一个 `<a href>` 的链接地址
+///
+/// `href` 属性返回按 `document.baseURI` 解析后的绝对地址，协议不可导出时返回 `None`
+pub(crate) fn read_cell_link(cell: &Element) -> Option<String> {
+    if cell.child_element_count() == 0 {
+        return None;
+    }
+    let anchor = cell
+        .query_selector("a[href]")
+        .ok()
+        .flatten()?
+        .dyn_into::<HtmlAnchorElement>()
+        .ok()?;
+    let url = anchor.href();
+    is_exportable_url(&url).then_some(url)
+}
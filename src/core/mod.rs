@@ -1,14 +1,62 @@
 /// 核心导出协调模块
 ///
 /// 提供统一的导出接口，协调各个导出模块
+mod column_spec;
+mod data_extractor;
+mod encoding;
 mod export_csv;
+mod export_html;
+mod export_json;
+mod export_markdown;
+mod export_ods;
 mod export_xlsx;
+mod file_output;
+mod form_controls;
+mod hyperlink;
+mod options;
+mod sanitize;
 mod table_extractor;
+mod type_inference;
+mod workbook;
 
-use export_csv::export_as_csv;
-use export_xlsx::export_as_xlsx;
+use crate::error::ExportError;
+use crate::i18n::Message;
+pub use column_spec::{ColumnRef, ColumnSelection, ColumnSpec, resolve_columns};
+pub use data_extractor::extract_js_data;
+pub use export_csv::{CsvQuoteStyle, LineTerminator, build_csv_bytes};
+pub(crate) use export_csv::{csv_writer, finish_csv};
+pub(crate) use export_html::HtmlTableReader;
+pub use export_html::{
+    HtmlCell, HtmlRow, HtmlSection, HtmlSectionKind, HtmlTable, build_html_bytes,
+};
+pub use export_json::{JsonLayout, build_json_bytes};
+pub use export_markdown::{build_markdown_bytes, escape_cell};
+pub use export_ods::build_ods_bytes;
+use export_ods::build_ods_workbook_bytes;
+use export_xlsx::build_workbook_bytes;
+pub use export_xlsx::build_xlsx_bytes;
+pub(crate) use export_xlsx::{XlsxSheetWriter, save_workbook};
+use file_output::create_file;
+pub(crate) use file_output::{create_blob, download_bytes, get_document, resolve_filename};
+pub use form_controls::{FormValue, form_cell_value};
+pub use hyperlink::{LinkFormat, is_exportable_url, link_text};
 use table_extractor::extract_table_data;
+use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::*;
+use workbook::extract_workbook_sheets;
+
+pub use data_extractor::format_number;
+pub use encoding::{CsvEncoding, encode_text};
+pub use options::ExportOptions;
+pub use sanitize::{FormulaSanitization, is_formula_like, sanitize_row};
+pub use table_extractor::{
+    ColumnAlign, MergeRange, RawCell, SpanGrid, SpanMode, TableData, build_table_data,
+    select_row_columns,
+};
+pub(crate) use table_extractor::{
+    RowReader, SectionTracker, detect_header_rows, find_table, read_column_alignments,
+};
+pub use type_inference::{CellType, CellValue, infer_cell_value, resolve_cell_value};
 
 /// 导出格式枚举
 #[wasm_bindgen]
@@ -19,6 +67,50 @@ pub enum ExportFormat {
     Csv,
     /// Excel XLSX 格式
     Xlsx,
+    /// TSV 格式（制表符分隔，包含制表符或换行的单元格会加引号）
+    Tsv,
+    /// JSON 格式（整个表格为一个数组）
+    Json,
+    /// NDJSON 格式（每行一个 JSON 值，适合大表格的流式处理）
+    Ndjson,
+    /// Markdown 格式（GitHub 风格表格）
+    Markdown,
+    /// OpenDocument 电子表格 ODS 格式（LibreOffice 等 ODF 办公软件）
+    Ods,
+    /// 独立的 HTML 文件，保留表格结构和合并单元格
+    Html,
+}
+
+impl ExportFormat {
+    /// 该格式的文件扩展名（不含点）
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::Tsv => "tsv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Ods => "ods",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    /// 该格式的 MIME 类型（文本格式为 UTF-8 编码时）
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv;charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+            ExportFormat::Tsv => "text/tab-separated-values;charset=utf-8",
+            ExportFormat::Json => "application/json;charset=utf-8",
+            ExportFormat::Ndjson => "application/x-ndjson;charset=utf-8",
+            ExportFormat::Markdown => "text/markdown;charset=utf-8",
+            ExportFormat::Ods => "application/vnd.oasis.opendocument.spreadsheet",
+            ExportFormat::Html => "text/html;charset=utf-8",
+        }
+    }
 }
 
 /// 统一的表格导出函数（带进度回调）
@@ -28,12 +120,12 @@ pub enum ExportFormat {
 /// # 参数
 /// * `table_id` - 要导出的 HTML 表格元素的 ID
 /// * `filename` - 可选的导出文件名（不包含扩展名时会自动添加）
-/// * `format` - 导出格式（Csv 或 Xlsx），默认为 Csv
+/// * `format` - 导出格式（见 `ExportFormat`），默认为 Csv
 /// * `progress_callback` - 可选的进度回调函数，接收 0-100 的进度值
 ///
 /// # 返回值
 /// * `Ok(())` - 导出成功
-/// * `Err(JsValue)` - 导出失败，包含错误信息
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
 ///
 /// # 示例
 /// ```javascript
@@ -54,6 +146,10 @@ pub enum ExportFormat {
 ///     document.getElementById('progress').style.width = `${progress}%`;
 /// });
 /// ```
+///
+/// # 注意
+/// 合并单元格展开方式（`span_mode`）、按列类型（`column_types`）等其他配置通过
+/// `export_table_with_options` 设置
 #[wasm_bindgen]
 pub fn export_table(
     table_id: &str,
@@ -61,20 +157,513 @@ pub fn export_table(
     format: Option<ExportFormat>,
     progress_callback: Option<js_sys::Function>,
 ) -> Result<(), JsValue> {
-    let format = format.unwrap_or_default();
+    let options = ExportOptions {
+        format: format.unwrap_or_default(),
+        filename,
+        progress_callback,
+        ..ExportOptions::default()
+    };
+
+    export_table_with_options(table_id, &options)
+}
+
+/// 使用配置对象导出表格
+///
+/// 与 `export_table` 相同，但所有配置（格式、文件名、工作表名、分隔符、
+/// 表头处理、列选择、回调等）都通过 `ExportOptions` 传入
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `options` - 导出配置
+///
+/// # 返回值
+/// * `Ok(())` - 导出成功
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+///
+/// # 示例
+/// ```javascript
+/// const options = new ExportOptions();
+/// options.format = ExportFormat.Xlsx;
+/// options.filename = '报表';
+/// options.include_header = false;
+/// export_table_with_options('my-table', options);
+/// ```
+#[wasm_bindgen]
+pub fn export_table_with_options(table_id: &str, options: &ExportOptions) -> Result<(), JsValue> {
+    download_table(table_id, options).map_err(|e| options.js_error(e))
+}
+
+/// 导出表格并返回文件字节（不触发下载）
+///
+/// 适用于上传到后端、作为附件或预览等需要自行处理文件内容的场景
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `format` - 导出格式（见 `ExportFormat`），默认为 Csv
+/// * `progress_callback` - 可选的进度回调函数，接收 0-100 的进度值
+///
+/// # 返回值
+/// * `Ok(Uint8Array)` - 文件内容
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+///
+/// # 示例
+/// ```javascript
+/// const bytes = export_table_to_bytes('my-table', ExportFormat.Xlsx);
+/// await fetch('/api/upload', { method: 'POST', body: bytes });
+/// ```
+#[wasm_bindgen]
+pub fn export_table_to_bytes(
+    table_id: &str,
+    format: Option<ExportFormat>,
+    progress_callback: Option<js_sys::Function>,
+) -> Result<js_sys::Uint8Array, JsValue> {
+    let options = ExportOptions {
+        format: format.unwrap_or_default(),
+        progress_callback,
+        ..ExportOptions::default()
+    };
+
+    export_table_to_bytes_with_options(table_id, &options)
+}
+
+/// 使用配置对象导出表格并返回文件字节（不触发下载）
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `options` - 导出配置（`filename` 被忽略）
+///
+/// # 返回值
+/// * `Ok(Uint8Array)` - 文件内容
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+#[wasm_bindgen]
+pub fn export_table_to_bytes_with_options(
+    table_id: &str,
+    options: &ExportOptions,
+) -> Result<js_sys::Uint8Array, JsValue> {
+    let bytes = export_table_bytes(table_id, options).map_err(|e| options.js_error(e))?;
+    Ok(js_sys::Uint8Array::from(bytes.as_slice()))
+}
+
+/// 导出表格并返回 File 对象（不触发下载）
+///
+/// File 继承自 Blob，带有正确的 MIME 类型和经过验证的文件名，
+/// 可以直接放入 FormData 上传或通过 `URL.createObjectURL` 预览
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `filename` - 可选的文件名（不包含扩展名时会自动添加）
+/// * `format` - 导出格式（见 `ExportFormat`），默认为 Csv
+/// * `progress_callback` - 可选的进度回调函数，接收 0-100 的进度值
+///
+/// # 返回值
+/// * `Ok(File)` - 导出的文件
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+///
+/// # 示例
+/// ```javascript
+/// const file = export_table_to_blob('my-table', '报表', ExportFormat.Xlsx);
+/// const form = new FormData();
+/// form.append('file', file, file.name);
+/// ```
+#[wasm_bindgen]
+pub fn export_table_to_blob(
+    table_id: &str,
+    filename: Option<String>,
+    format: Option<ExportFormat>,
+    progress_callback: Option<js_sys::Function>,
+) -> Result<web_sys::File, JsValue> {
+    let options = ExportOptions {
+        format: format.unwrap_or_default(),
+        filename,
+        progress_callback,
+        ..ExportOptions::default()
+    };
+
+    export_table_to_blob_with_options(table_id, &options)
+}
+
+/// 使用配置对象导出表格并返回 File 对象（不触发下载）
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `options` - 导出配置
+///
+/// # 返回值
+/// * `Ok(File)` - 导出的文件
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+#[wasm_bindgen]
+pub fn export_table_to_blob_with_options(
+    table_id: &str,
+    options: &ExportOptions,
+) -> Result<web_sys::File, JsValue> {
+    table_file(table_id, options).map_err(|e| options.js_error(e))
+}
+
+/// 使用配置对象导出表格并返回文本（不触发下载）
+///
+/// 仅支持文本格式（CSV、TSV、JSON、NDJSON、Markdown、HTML），适合复制到剪贴板或嵌入页面。
+/// 返回的是 JavaScript 字符串，因此忽略 `encoding` 和 `bom` 选项
+///
+/// # 参数
+/// * `table_id` - 要导出的 HTML 表格元素的 ID
+/// * `options` - 导出配置（`filename` 被忽略）
+///
+/// # 返回值
+/// * `Ok(String)` - 导出的文本
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象；
+///   格式为 Xlsx 或 Ods 时错误码为 `INVALID_ARGUMENT`
+///
+/// # 示例
+/// ```javascript
+/// const options = new ExportOptions();
+/// options.format = ExportFormat.Markdown;
+/// const markdown = export_table_to_string('my-table', options);
+/// ```
+#[wasm_bindgen]
+pub fn export_table_to_string(table_id: &str, options: &ExportOptions) -> Result<String, JsValue> {
+    table_text(table_id, options).map_err(|e| options.js_error(e))
+}
+
+/// 导出表格并触发下载
+fn download_table(table_id: &str, options: &ExportOptions) -> Result<(), ExportError> {
+    let filename = resolve_filename(
+        options.filename.clone(),
+        options.format.extension(),
+        options.resolved_locale(),
+    )?;
+    let bytes = export_table_bytes(table_id, options)?;
+    download_bytes(&bytes, &options.mime_type(), &filename)
+}
+
+/// 导出表格并创建 File 对象
+fn table_file(table_id: &str, options: &ExportOptions) -> Result<web_sys::File, ExportError> {
+    let filename = resolve_filename(
+        options.filename.clone(),
+        options.format.extension(),
+        options.resolved_locale(),
+    )?;
+    let bytes = export_table_bytes(table_id, options)?;
+    create_file(&bytes, &options.mime_type(), &filename)
+}
+
+/// 导出表格为 UTF-8 文本
+fn table_text(table_id: &str, options: &ExportOptions) -> Result<String, ExportError> {
+    if matches!(options.format, ExportFormat::Xlsx | ExportFormat::Ods) {
+        return Err(ExportError::InvalidArgument {
+            message: Message::NotTextFormat {
+                format: options.format.extension(),
+            },
+        });
+    }
+
+    let options = &ExportOptions {
+        encoding: CsvEncoding::Utf8,
+        bom: false,
+        ..options.clone()
+    };
+    let bytes = export_table_bytes(table_id, options)?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
 
-    // 输入验证
-    if table_id.is_empty() {
-        return Err(JsValue::from_str("表格 ID 不能为空"));
+/// 提取表格数据并生成指定格式的文件字节
+fn export_table_bytes(table_id: &str, options: &ExportOptions) -> Result<Vec<u8>, ExportError> {
+    // HTML 保留原表格的结构，直接读取 DOM
+    if options.format == ExportFormat::Html {
+        return export_table_html(table_id, options);
     }
 
     // 提取表格数据
-    let table_data = extract_table_data(table_id)?;
+    let mut table_data = extract_table_data(table_id, options)?;
+    apply_options(&mut table_data, options)?;
+
+    render_table_data(&table_data, options)
+}
+
+/// 提取表格并生成剪贴板内容
+///
+/// 按 TSV 处理与格式相关的默认值（如公式注入防护），见 [`build_clipboard_content`]
+///
+/// # 返回值
+/// * `Ok((String, String))` - TSV 文本与 HTML 文本
+/// * `Err(ExportError)` - 提取或生成失败
+pub(crate) fn clipboard_content(
+    table_id: &str,
+    options: &ExportOptions,
+) -> Result<(String, String), ExportError> {
+    let options = &ExportOptions {
+        format: ExportFormat::Tsv,
+        encoding: CsvEncoding::Utf8,
+        bom: false,
+        progress_callback: None,
+        ..options.clone()
+    };
+
+    let table_data = extract_table_data(table_id, options)?;
+    build_clipboard_content(table_data, options)
+}
+
+/// 由提取的表格数据生成剪贴板内容
+///
+/// TSV 与 HTML 使用同一份调整后的表格数据（表头处理、列选择）。公式注入防护只作用于 TSV：
+/// HTML 中的单元格按文本粘贴，添加的 `'` 前缀反而会显示出来
+///
+/// # 参数
+/// * `table_data` - 从 DOM 提取、尚未按配置调整的表格数据
+/// * `options` - 导出配置，格式应为 TSV
+///
+/// # 返回值
+/// * `Ok((String, String))` - TSV 文本与 HTML 文本
+/// * `Err(ExportError)` - 调整或生成失败，`Reject` 模式下发现可疑单元格时错误码为 `FORMULA_INJECTION`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn build_clipboard_content(
+    mut table_data: TableData,
+    options: &ExportOptions,
+) -> Result<(String, String), ExportError> {
+    let unsanitized = ExportOptions {
+        formula_sanitization: Some(FormulaSanitization::Off),
+        ..options.clone()
+    };
+    apply_options(&mut table_data, &unsanitized)?;
+    let html = build_html_bytes(&HtmlTable::from_table_data(&table_data), options)?;
+
+    let sanitization = options.resolved_sanitization();
+    for (index, row) in table_data.rows.iter_mut().enumerate() {
+        sanitize_row(row, index, sanitization)?;
+    }
+    let text = build_csv_bytes(&table_data, options)?;
+
+    Ok((
+        String::from_utf8_lossy(&text).into_owned(),
+        String::from_utf8_lossy(&html).into_owned(),
+    ))
+}
+
+/// 读取表格结构并生成 HTML 文件字节
+fn export_table_html(table_id: &str, options: &ExportOptions) -> Result<Vec<u8>, ExportError> {
+    options.check_html_snapshot()?;
+    let table = find_table(table_id)?;
+    let mut reader = HtmlTableReader::new(&table, options.inline_styles)?;
 
-    // 根据格式导出
-    match format {
-        ExportFormat::Csv => export_as_csv(table_data, filename, progress_callback),
-        ExportFormat::Xlsx => export_as_xlsx(table_data, filename, progress_callback),
+    let rows = table.rows();
+    for i in 0..rows.length() {
+        let row = rows
+            .get_with_index(i)
+            .and_then(|row| row.dyn_into::<web_sys::HtmlTableRowElement>().ok())
+            .ok_or(ExportError::ReadFailed {
+                row: i as usize,
+                column: None,
+            })?;
+        reader.push_row(&row, i as usize)?;
     }
+
+    build_html_bytes(&reader.finish(), options)
+}
+
+/// 按配置调整表格数据（表头行数、是否包含表头、列选择与重命名、公式注入防护）
+pub(crate) fn apply_options(
+    table_data: &mut TableData,
+    options: &ExportOptions,
+) -> Result<(), ExportError> {
+    if let Some(header_rows) = options.header_rows {
+        table_data.header_rows = (header_rows as usize).min(table_data.rows.len());
+    }
+
+    // 按表头文本引用的列需要在移除表头前解析
+    let header_rows = table_data.header_rows.min(table_data.rows.len());
+    let width = table_data.rows.first().map_or(0, Vec::len);
+    let selection = options.column_selection(&table_data.rows[..header_rows], width)?;
+
+    if !options.include_header {
+        table_data.remove_header_rows();
+    }
+
+    if let Some(selection) = selection {
+        table_data.select_columns(&selection.columns)?;
+        table_data.rename_headers(&selection.titles);
+    }
+
+    let sanitization = options.text_sanitization()?;
+    for (index, row) in table_data.rows.iter_mut().enumerate() {
+        sanitize_row(row, index, sanitization)?;
+    }
+
+    if table_data.rows.is_empty() {
+        return Err(ExportError::EmptyTable);
+    }
+
+    Ok(())
+}
+
+/// 将表格数据生成为指定格式的文件字节
+fn render_table_data(
+    table_data: &TableData,
+    options: &ExportOptions,
+) -> Result<Vec<u8>, ExportError> {
+    match options.format {
+        ExportFormat::Csv | ExportFormat::Tsv => build_csv_bytes(table_data, options),
+        ExportFormat::Xlsx => build_xlsx_bytes(table_data, options),
+        ExportFormat::Json | ExportFormat::Ndjson => build_json_bytes(table_data, options),
+        ExportFormat::Markdown => build_markdown_bytes(table_data, options),
+        ExportFormat::Ods => build_ods_bytes(table_data, options),
+        ExportFormat::Html => build_html_bytes(&HtmlTable::from_table_data(table_data), options),
+    }
+}
+
+/// 从 JavaScript 数据导出（无需 DOM 表格）
+///
+/// 与 `export_table` 使用相同的 CSV/Excel 导出流程，适用于虚拟滚动等
+/// 大部分行不在 DOM 中的场景，可以导出完整数据集而不仅是可见行
+///
+/// # 参数
+/// * `data` - 数组的数组（每个内层数组为一行），或对象数组
+/// * `columns` - 可选的列定义，元素为字符串或 `{ key, title }` 对象，提供时会生成表头行；
+///   未提供时数组的数组以第一行为表头
+/// * `filename` - 可选的导出文件名（不包含扩展名时会自动添加）
+/// * `format` - 导出格式（见 `ExportFormat`），默认为 Csv
+/// * `progress_callback` - 可选的进度回调函数，接收 0-100 的进度值
+///
+/// # 返回值
+/// * `Ok(())` - 导出成功
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+///
+/// # 示例
+/// ```javascript
+/// import init, { export_data, ExportFormat } from './pkg/excel_exporter.js';
+/// await init();
+///
+/// // 数组的数组（第一行即表头）
+/// export_data([['姓名', '年龄'], ['张三', 28]], null, '人员.csv');
+///
+/// // 对象数组 + 列定义
+/// export_data(
+///     rows,
+///     [{ key: 'name', title: '姓名' }, { key: 'age', title: '年龄' }],
+///     '人员',
+///     ExportFormat.Xlsx,
+/// );
+/// ```
+#[wasm_bindgen]
+pub fn export_data(
+    data: JsValue,
+    columns: Option<js_sys::Array>,
+    filename: Option<String>,
+    format: Option<ExportFormat>,
+    progress_callback: Option<js_sys::Function>,
+) -> Result<(), JsValue> {
+    let options = ExportOptions {
+        format: format.unwrap_or_default(),
+        filename,
+        progress_callback,
+        ..ExportOptions::default()
+    };
+
+    export_data_with_options(data, columns, &options)
+}
+
+/// 使用配置对象从 JavaScript 数据导出（无需 DOM 表格）
+///
+/// # 参数
+/// * `data` - 数组的数组（每个内层数组为一行），或对象数组
+/// * `columns` - 可选的列定义，元素为字符串或 `{ key, title }` 对象，提供时会生成表头行；
+///   未提供时数组的数组以第一行为表头
+/// * `options` - 导出配置（`span_mode`、`skip_hidden` 和 `tbody_id` 被忽略）
+///
+/// # 返回值
+/// * `Ok(())` - 导出成功
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+#[wasm_bindgen]
+pub fn export_data_with_options(
+    data: JsValue,
+    columns: Option<js_sys::Array>,
+    options: &ExportOptions,
+) -> Result<(), JsValue> {
+    download_data(&data, columns, options).map_err(|e| options.js_error(e))
+}
+
+/// 从 JavaScript 数据导出并触发下载
+fn download_data(
+    data: &JsValue,
+    columns: Option<js_sys::Array>,
+    options: &ExportOptions,
+) -> Result<(), ExportError> {
+    let filename = resolve_filename(
+        options.filename.clone(),
+        options.format.extension(),
+        options.resolved_locale(),
+    )?;
+
+    // 提取数据
+    let mut table_data = extract_js_data(data, columns)?;
+    apply_options(&mut table_data, options)?;
+
+    let bytes = render_table_data(&table_data, options)?;
+    download_bytes(&bytes, &options.mime_type(), &filename)
+}
+
+/// 将多个表格导出为一个包含多个工作表的 Excel 工作簿
+///
+/// 每个工作表描述对应工作簿中的一个工作表，数据可以来自 DOM 表格或 JavaScript 数组。
+/// `options.format` 为 `ExportFormat.Ods` 时生成 ODS 文件，其余格式均生成 XLSX。
+/// 工作表名称需符合 Excel 规则：不超过 31 个字符，不包含 `[]:*?/\`，且互不重复（不区分大小写）
+///
+/// # 参数
+/// * `sheets` - 工作表描述数组，元素为 `{ sheet_name?, table_id }` 或 `{ sheet_name?, data, columns? }`
+/// * `options` - 导出配置，表头处理、列选择和列类型对每个工作表生效（`sheet_name` 被忽略）
+///
+/// # 返回值
+/// * `Ok(())` - 导出成功
+/// * `Err(JsValue)` - 导出失败，为带有 `code` 错误码的 `Error` 对象
+///
+/// # 示例
+/// ```javascript
+/// import { ExportOptions, export_workbook } from './pkg/excel_exporter.js';
+///
+/// const options = new ExportOptions();
+/// options.filename = '仪表盘';
+///
+/// export_workbook([
+///     { sheet_name: '汇总', table_id: 'summary-table' },
+///     { sheet_name: '明细', table_id: 'detail-table' },
+///     { sheet_name: '异常', data: exceptions, columns: ['编号', '原因'] },
+/// ], options);
+/// ```
+#[wasm_bindgen]
+pub fn export_workbook(sheets: js_sys::Array, options: &ExportOptions) -> Result<(), JsValue> {
+    download_workbook(&sheets, options).map_err(|e| options.js_error(e))
 }
 
+/// 导出多工作表工作簿并触发下载
+fn download_workbook(sheets: &js_sys::Array, options: &ExportOptions) -> Result<(), ExportError> {
+    // 工作簿为 ODS 或 Excel 格式，与格式相关的默认值（如公式注入防护）按该格式处理
+    let format = match options.format {
+        ExportFormat::Ods => ExportFormat::Ods,
+        _ => ExportFormat::Xlsx,
+    };
+    let options = &ExportOptions {
+        format,
+        ..options.clone()
+    };
+    let filename = resolve_filename(
+        options.filename.clone(),
+        format.extension(),
+        options.resolved_locale(),
+    )?;
+
+    let mut sheets = extract_workbook_sheets(sheets, options)?;
+    for (_, table_data) in sheets.iter_mut() {
+        apply_options(table_data, options)?;
+    }
+
+    let sheets: Vec<(String, &TableData)> = sheets
+        .iter()
+        .map(|(name, table_data)| (name.clone(), table_data))
+        .collect();
+    let bytes = match format {
+        ExportFormat::Ods => build_ods_workbook_bytes(&sheets, options)?,
+        _ => build_workbook_bytes(&sheets, options)?,
+    };
+    download_bytes(&bytes, format.mime_type(), &filename)
+}
@@ -0,0 +1,611 @@
+/// 导出配置模块
+///
+/// 提供所有导出路径共用的 `ExportOptions` 配置对象，避免导出函数的位置参数不断增长
+use super::ExportFormat;
+use super::column_spec::{
+    ColumnRef, ColumnSelection, ColumnSpec, column_refs_to_js, column_spec_to_js,
+    parse_column_refs, parse_column_spec, resolve_columns,
+};
+use super::encoding::CsvEncoding;
+use super::export_csv::{CsvQuoteStyle, LineTerminator};
+use super::export_json::JsonLayout;
+use super::hyperlink::LinkFormat;
+use super::sanitize::FormulaSanitization;
+use super::table_extractor::SpanMode;
+use super::type_inference::CellType;
+use crate::error::ExportError;
+use crate::i18n::{Locale, Message, get_locale};
+use wasm_bindgen::prelude::*;
+
+/// 导出配置
+///
+/// 在 JavaScript 中通过 `new ExportOptions()` 创建，再设置需要的属性，
+/// 未设置的属性使用默认值
+///
+/// # 示例
+/// ```javascript
+/// import { ExportOptions, ExportFormat, export_table_with_options } from './pkg/excel_exporter.js';
+///
+/// const options = new ExportOptions();
+/// options.format = ExportFormat.Xlsx;
+/// options.filename = '销售报表';
+/// options.sheet_name = '汇总';
+/// options.columns = [2, 0, 1];  // 只导出第 3、1、2 列，并按此顺序排列
+/// options.progress_callback = (progress) => console.log(progress);
+///
+/// export_table_with_options('sales-table', options);
+/// ```
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub(crate) format: ExportFormat,
+    pub(crate) filename: Option<String>,
+    pub(crate) sheet_name: Option<String>,
+    pub(crate) delimiter: u8,
+    pub(crate) quote: u8,
+    pub(crate) quote_style: CsvQuoteStyle,
+    pub(crate) line_terminator: LineTerminator,
+    pub(crate) double_quote: bool,
+    pub(crate) escape: u8,
+    pub(crate) encoding: CsvEncoding,
+    pub(crate) bom: bool,
+    pub(crate) link_format: LinkFormat,
+    pub(crate) formula_sanitization: Option<FormulaSanitization>,
+    pub(crate) json_layout: JsonLayout,
+    pub(crate) json_infer_types: bool,
+    pub(crate) inline_styles: bool,
+    pub(crate) header_rows: Option<u32>,
+    pub(crate) include_header: bool,
+    pub(crate) columns: Option<Vec<u32>>,
+    pub(crate) column_spec: Option<Vec<ColumnSpec>>,
+    pub(crate) exclude_columns: Vec<ColumnRef>,
+    pub(crate) column_types: Vec<CellType>,
+    pub(crate) span_mode: SpanMode,
+    pub(crate) skip_hidden: bool,
+    pub(crate) tbody_id: Option<String>,
+    pub(crate) batch_size: u32,
+    pub(crate) progress_callback: Option<js_sys::Function>,
+    pub(crate) signal: Option<web_sys::AbortSignal>,
+    pub(crate) locale: Option<Locale>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::default(),
+            filename: None,
+            sheet_name: None,
+            delimiter: b',',
+            quote: b'"',
+            quote_style: CsvQuoteStyle::default(),
+            line_terminator: LineTerminator::default(),
+            double_quote: true,
+            escape: b'\\',
+            encoding: CsvEncoding::default(),
+            bom: false,
+            link_format: LinkFormat::default(),
+            formula_sanitization: None,
+            json_layout: JsonLayout::default(),
+            json_infer_types: false,
+            inline_styles: false,
+            header_rows: None,
+            include_header: true,
+            columns: None,
+            column_spec: None,
+            exclude_columns: Vec::new(),
+            column_types: Vec::new(),
+            span_mode: SpanMode::default(),
+            skip_hidden: false,
+            tbody_id: None,
+            batch_size: 1000,
+            progress_callback: None,
+            signal: None,
+            locale: None,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl ExportOptions {
+    /// 创建默认配置
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 导出格式，默认为 Csv
+    #[wasm_bindgen(getter)]
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_format(&mut self, format: ExportFormat) {
+        self.format = format;
+    }
+
+    /// 导出文件名，不包含扩展名时会自动添加，默认为 `table_export.<扩展名>`（随语言变化）
+    #[wasm_bindgen(getter)]
+    pub fn filename(&self) -> Option<String> {
+        self.filename.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_filename(&mut self, filename: Option<String>) {
+        self.filename = filename;
+    }
+
+    /// Excel 工作表名称（仅 Xlsx），默认为 `Sheet1`
+    #[wasm_bindgen(getter)]
+    pub fn sheet_name(&self) -> Option<String> {
+        self.sheet_name.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_sheet_name(&mut self, sheet_name: Option<String>) {
+        self.sheet_name = sheet_name;
+    }
+
+    /// CSV 字段分隔符（单个 ASCII 字符），默认为 `,`；TSV 格式始终使用制表符
+    #[wasm_bindgen(getter)]
+    pub fn delimiter(&self) -> String {
+        (self.delimiter as char).to_string()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_delimiter(&mut self, delimiter: &str) -> Result<(), ExportError> {
+        self.delimiter = parse_ascii_char(delimiter, "delimiter")?;
+        Ok(())
+    }
+
+    /// CSV 引号字符（单个 ASCII 字符），默认为 `"`
+    #[wasm_bindgen(getter)]
+    pub fn quote(&self) -> String {
+        (self.quote as char).to_string()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_quote(&mut self, quote: &str) -> Result<(), ExportError> {
+        self.quote = parse_ascii_char(quote, "quote")?;
+        Ok(())
+    }
+
+    /// CSV 字段的引号策略，默认为 Necessary（仅在需要时加引号）
+    #[wasm_bindgen(getter)]
+    pub fn quote_style(&self) -> CsvQuoteStyle {
+        self.quote_style
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_quote_style(&mut self, quote_style: CsvQuoteStyle) {
+        self.quote_style = quote_style;
+    }
+
+    /// CSV 记录之间的换行符，默认为 Crlf
+    #[wasm_bindgen(getter)]
+    pub fn line_terminator(&self) -> LineTerminator {
+        self.line_terminator
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_line_terminator(&mut self, line_terminator: LineTerminator) {
+        self.line_terminator = line_terminator;
+    }
+
+    /// 字段中的引号是否通过重复引号转义（`""`），默认为 true；
+    /// 为 false 时改用 `escape` 字符转义
+    #[wasm_bindgen(getter)]
+    pub fn double_quote(&self) -> bool {
+        self.double_quote
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_double_quote(&mut self, double_quote: bool) {
+        self.double_quote = double_quote;
+    }
+
+    /// CSV 转义字符（单个 ASCII 字符），仅在 `double_quote` 为 false 时使用，默认为 `\`
+    #[wasm_bindgen(getter)]
+    pub fn escape(&self) -> String {
+        (self.escape as char).to_string()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_escape(&mut self, escape: &str) -> Result<(), ExportError> {
+        self.escape = parse_ascii_char(escape, "escape")?;
+        Ok(())
+    }
+
+    /// CSV 文件编码，默认为 Utf8
+    #[wasm_bindgen(getter)]
+    pub fn encoding(&self) -> CsvEncoding {
+        self.encoding
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_encoding(&mut self, encoding: CsvEncoding) {
+        self.encoding = encoding;
+    }
+
+    /// 是否在 UTF-8 编码的 CSV 开头添加 BOM，默认为 false
+    ///
+    /// Windows 上的 Excel 需要 BOM 才能正确识别 UTF-8 中的中文、日文等字符
+    #[wasm_bindgen(getter)]
+    pub fn bom(&self) -> bool {
+        self.bom
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_bom(&mut self, bom: bool) {
+        self.bom = bom;
+    }
+
+    /// CSV/TSV 中带链接（`<a href>`）单元格的输出方式，默认为 Text（只输出链接文本）
+    ///
+    /// XLSX 始终将链接写为超链接，不受此设置影响
+    #[wasm_bindgen(getter)]
+    pub fn link_format(&self) -> LinkFormat {
+        self.link_format
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_link_format(&mut self, link_format: LinkFormat) {
+        self.link_format = link_format;
+    }
+
+    /// 公式注入防护方式，未设置时 CSV/TSV 为 Prefix，其余格式为 Off
+    ///
+    /// 以 `=`、`+`、`-`、`@`、制表符或回车开头的单元格会被处理，合法的数字（如负数）除外。
+    /// Excel 的 Prefix 设置单元格的引号前缀属性，单元格文本中不会出现 `'`；
+    /// ODS 没有对应的属性，设置 Prefix 时导出返回 `INVALID_ARGUMENT` 错误
+    #[wasm_bindgen(getter)]
+    pub fn formula_sanitization(&self) -> Option<FormulaSanitization> {
+        self.formula_sanitization
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_formula_sanitization(&mut self, formula_sanitization: Option<FormulaSanitization>) {
+        self.formula_sanitization = formula_sanitization;
+    }
+
+    /// JSON/NDJSON 中每一行的表示方式，默认为 Objects（以表头为键的对象）
+    #[wasm_bindgen(getter)]
+    pub fn json_layout(&self) -> JsonLayout {
+        self.json_layout
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_json_layout(&mut self, json_layout: JsonLayout) {
+        self.json_layout = json_layout;
+    }
+
+    /// JSON/NDJSON 是否将数字、百分比和布尔值输出为对应的 JSON 类型，默认为 false（全部为字符串）
+    ///
+    /// 按 `column_types` 解析，类型为 Text 的列始终为字符串
+    #[wasm_bindgen(getter)]
+    pub fn json_infer_types(&self) -> bool {
+        self.json_infer_types
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_json_infer_types(&mut self, json_infer_types: bool) {
+        self.json_infer_types = json_infer_types;
+    }
+
+    /// HTML 是否将表格、行和单元格的计算样式（颜色、字体、边框、对齐等）内联到 `style` 属性，
+    /// 默认为 false（使用简单的默认边框样式）
+    #[wasm_bindgen(getter)]
+    pub fn inline_styles(&self) -> bool {
+        self.inline_styles
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_inline_styles(&mut self, inline_styles: bool) {
+        self.inline_styles = inline_styles;
+    }
+
+    /// 表头行数，未设置时自动检测（`<thead>` 的行数，否则首行全为 `<th>` 时为 1）
+    ///
+    /// 表头行在 Excel 中始终写为文本，不参与类型推断。导出 DOM 表格为 HTML 快照时不能设置，否则返回 `INVALID_ARGUMENT` 错误
+    #[wasm_bindgen(getter)]
+    pub fn header_rows(&self) -> Option<u32> {
+        self.header_rows
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_header_rows(&mut self, header_rows: Option<u32>) {
+        self.header_rows = header_rows;
+    }
+
+    /// 是否导出表头行，默认为 true
+    ///
+    /// 导出 DOM 表格为 HTML 快照时不能设置为 false，否则返回 `INVALID_ARGUMENT` 错误
+    #[wasm_bindgen(getter)]
+    pub fn include_header(&self) -> bool {
+        self.include_header
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_include_header(&mut self, include_header: bool) {
+        self.include_header = include_header;
+    }
+
+    /// 要导出的列索引（从 0 开始），按数组顺序输出，未设置时导出全部列；不能与 `column_spec` 同时设置
+    ///
+    /// 导出 DOM 表格为 HTML 快照时不能设置，否则返回 `INVALID_ARGUMENT` 错误
+    #[wasm_bindgen(getter)]
+    pub fn columns(&self) -> Option<Vec<u32>> {
+        self.columns.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_columns(&mut self, columns: Option<Vec<u32>>) {
+        self.columns = columns;
+    }
+
+    /// 按输出顺序排列的列配置，未设置时导出全部列；不能与 `columns` 同时设置
+    ///
+    /// 元素为列索引、表头文本，或 `{ column, title }` 对象（`column` 为列索引或表头文本，
+    /// `title` 为导出时的新表头）。表头文本与任一表头行中该列的文本比较，取第一个匹配的列。
+    /// 导出 DOM 表格为 HTML 快照时不能设置，否则返回 `INVALID_ARGUMENT` 错误
+    #[wasm_bindgen(getter)]
+    pub fn column_spec(&self) -> JsValue {
+        match self.column_spec {
+            Some(ref spec) => column_spec_to_js(spec),
+            None => JsValue::UNDEFINED,
+        }
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_column_spec(&mut self, column_spec: JsValue) -> Result<(), ExportError> {
+        self.column_spec = parse_column_spec(&column_spec)?;
+        Ok(())
+    }
+
+    /// 要排除的列（列索引或表头文本），在 `columns`/`column_spec` 之后应用
+    ///
+    /// 导出 DOM 表格为 HTML 快照时不能设置，否则返回 `INVALID_ARGUMENT` 错误
+    #[wasm_bindgen(getter)]
+    pub fn exclude_columns(&self) -> JsValue {
+        column_refs_to_js(&self.exclude_columns)
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_exclude_columns(&mut self, exclude_columns: JsValue) -> Result<(), ExportError> {
+        self.exclude_columns = parse_column_refs(&exclude_columns)?;
+        Ok(())
+    }
+
+    /// 按列覆盖的单元格类型（仅 Xlsx），索引对应导出后的列顺序
+    #[wasm_bindgen(getter)]
+    pub fn column_types(&self) -> Vec<CellType> {
+        self.column_types.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_column_types(&mut self, column_types: Option<Vec<CellType>>) {
+        self.column_types = column_types.unwrap_or_default();
+    }
+
+    /// 合并单元格展开方式，默认为 Blank
+    #[wasm_bindgen(getter)]
+    pub fn span_mode(&self) -> SpanMode {
+        self.span_mode
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_span_mode(&mut self, span_mode: SpanMode) {
+        self.span_mode = span_mode;
+    }
+
+    /// 是否跳过隐藏的行和单元格，只导出用户当前看到的内容，默认为 false
+    ///
+    /// 带有 `hidden` 属性、`aria-hidden="true"`，或计算样式为 `display: none`、
+    /// `visibility: collapse` 的行（包括所在的 thead/tbody/tfoot）和单元格被视为隐藏。
+    /// 需要为每行计算样式，大表格上会增加提取时间；对 HTML 快照不生效
+    #[wasm_bindgen(getter)]
+    pub fn skip_hidden(&self) -> bool {
+        self.skip_hidden
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_skip_hidden(&mut self, skip_hidden: bool) {
+        self.skip_hidden = skip_hidden;
+    }
+
+    /// 分批导出时额外读取的 tbody 元素 ID
+    #[wasm_bindgen(getter)]
+    pub fn tbody_id(&self) -> Option<String> {
+        self.tbody_id.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_tbody_id(&mut self, tbody_id: Option<String>) {
+        self.tbody_id = tbody_id;
+    }
+
+    /// 分批导出时每批处理的行数，默认为 1000
+    #[wasm_bindgen(getter)]
+    pub fn batch_size(&self) -> u32 {
+        self.batch_size
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_batch_size(&mut self, batch_size: u32) -> Result<(), ExportError> {
+        if batch_size == 0 {
+            return Err(ExportError::invalid_argument(Message::BatchSizeZero));
+        }
+        self.batch_size = batch_size;
+        Ok(())
+    }
+
+    /// 进度回调函数，接收 0-100 的进度值
+    #[wasm_bindgen(getter)]
+    pub fn progress_callback(&self) -> Option<js_sys::Function> {
+        self.progress_callback.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_progress_callback(&mut self, progress_callback: Option<js_sys::Function>) {
+        self.progress_callback = progress_callback;
+    }
+
+    /// 用于取消分批导出的 `AbortSignal`，在批次之间检查
+    #[wasm_bindgen(getter)]
+    pub fn signal(&self) -> Option<web_sys::AbortSignal> {
+        self.signal.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_signal(&mut self, signal: Option<web_sys::AbortSignal>) {
+        self.signal = signal;
+    }
+
+    /// 本次导出的消息语言，未设置时使用 `set_locale` 设置的全局语言
+    #[wasm_bindgen(getter)]
+    pub fn locale(&self) -> Option<Locale> {
+        self.locale
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_locale(&mut self, locale: Option<Locale>) {
+        self.locale = locale;
+    }
+}
+
+impl ExportOptions {
+    /// 本次导出实际使用的消息语言
+    pub(crate) fn resolved_locale(&self) -> Locale {
+        self.locale.unwrap_or_else(get_locale)
+    }
+
+    /// 按表头解析 `columns`、`column_spec` 和 `exclude_columns`，均未设置时返回 `None`
+    ///
+    /// # 参数
+    /// * `header_rows` - 表头行（展开合并单元格后）
+    /// * `width` - 表格列数
+    pub(crate) fn column_selection(
+        &self,
+        header_rows: &[Vec<String>],
+        width: usize,
+    ) -> Result<Option<ColumnSelection>, ExportError> {
+        let spec = match (&self.columns, &self.column_spec) {
+            (Some(_), Some(_)) => {
+                return Err(ExportError::invalid_argument(
+                    Message::ColumnOptionsConflict,
+                ));
+            }
+            (Some(columns), None) => columns
+                .iter()
+                .map(|&c| ColumnSpec {
+                    column: ColumnRef::Index(c as usize),
+                    title: None,
+                })
+                .collect(),
+            (None, Some(spec)) => spec.clone(),
+            (None, None) if self.exclude_columns.is_empty() => return Ok(None),
+            (None, None) => Vec::new(),
+        };
+        resolve_columns(header_rows, width, &spec, &self.exclude_columns).map(Some)
+    }
+
+    /// 检查导出 DOM 表格为 HTML 快照时不支持的配置
+    ///
+    /// HTML 快照保留原表格的结构和合并单元格，不应用表头和列选择配置；
+    /// 设置了这些配置时返回错误，而不是静默忽略
+    ///
+    /// # 返回值
+    /// * `Ok(())` - 没有设置不支持的配置
+    /// * `Err(ExportError)` - 错误码为 `INVALID_ARGUMENT`，信息中包含第一个不支持的配置名
+    ///
+    /// # 注意
+    /// 这个函数主要供内部使用，但也导出以便测试
+    #[doc(hidden)]
+    pub fn check_html_snapshot(&self) -> Result<(), ExportError> {
+        let unsupported = [
+            ("header_rows", self.header_rows.is_some()),
+            ("include_header", !self.include_header),
+            ("columns", self.columns.is_some()),
+            ("column_spec", self.column_spec.is_some()),
+            ("exclude_columns", !self.exclude_columns.is_empty()),
+        ]
+        .into_iter()
+        .find(|&(_, set)| set);
+
+        match unsupported {
+            Some((option, _)) => Err(ExportError::invalid_argument(
+                Message::HtmlOptionUnsupported { option },
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// 本次导出实际使用的公式注入防护方式
+    ///
+    /// # 注意
+    /// 这个函数主要供内部使用，但也导出以便测试
+    #[doc(hidden)]
+    pub fn resolved_sanitization(&self) -> FormulaSanitization {
+        match (self.format, self.formula_sanitization) {
+            (_, Some(sanitization)) => sanitization,
+            (ExportFormat::Csv | ExportFormat::Tsv, None) => FormulaSanitization::Prefix,
+            (_, None) => FormulaSanitization::Off,
+        }
+    }
+
+    /// 写入前对单元格文本使用的防护方式
+    ///
+    /// Excel 的 `Prefix` 由写入时的单元格格式实现，不修改文本；
+    /// ODS 的文本单元格没有引号前缀属性，无法实现 `Prefix`
+    ///
+    /// # 返回值
+    /// * `Ok(FormulaSanitization)` - 对单元格文本使用的防护方式
+    /// * `Err(ExportError)` - ODS 导出设置了 `Prefix`
+    ///
+    /// # 注意
+    /// 这个函数主要供内部使用，但也导出以便测试
+    #[doc(hidden)]
+    pub fn text_sanitization(&self) -> Result<FormulaSanitization, ExportError> {
+        match (self.format, self.resolved_sanitization()) {
+            (ExportFormat::Xlsx, FormulaSanitization::Prefix) => Ok(FormulaSanitization::Off),
+            (ExportFormat::Ods, FormulaSanitization::Prefix) => {
+                Err(ExportError::invalid_argument(Message::PrefixUnsupported {
+                    format: self.format.extension(),
+                }))
+            }
+            (_, sanitization) => Ok(sanitization),
+        }
+    }
+
+    /// 导出文件的 MIME 类型，CSV 的 charset 与编码一致
+    pub(crate) fn mime_type(&self) -> String {
+        match self.format {
+            ExportFormat::Csv => format!("text/csv;charset={}", self.encoding.label()),
+            ExportFormat::Tsv => format!(
+                "text/tab-separated-values;charset={}",
+                self.encoding.label()
+            ),
+            _ => self.format.mime_type().to_string(),
+        }
+    }
+
+    /// 将导出错误转换为 JavaScript `Error` 对象，错误信息使用本次导出的语言
+    pub(crate) fn js_error(&self, error: ExportError) -> JsValue {
+        error.into_js(self.resolved_locale())
+    }
+}
+
+/// 解析单个 ASCII 字符
+///
+/// # 参数
+/// * `value` - 用户提供的字符串
+/// * `option` - 选项名称（用于错误信息）
+fn parse_ascii_char(value: &str, option: &'static str) -> Result<u8, ExportError> {
+    match value.as_bytes() {
+        [byte] if byte.is_ascii() => Ok(*byte),
+        _ => Err(ExportError::invalid_argument(Message::NotSingleAsciiChar {
+            option,
+            value: value.to_string(),
+        })),
+    }
+}
@@ -0,0 +1,116 @@
+/// 公式注入防护模块
+///
+/// 以 `=`、`+`、`-`、`@`、制表符或回车开头的单元格在电子表格软件中可能被当作公式执行
+/// （CSV/公式注入）。本模块按配置为这类单元格添加前缀、去除危险字符或拒绝导出，
+/// 合法的数字（如 `-12.5`、`+3%`、`-2e5`）不受影响
+use super::type_inference::{CellValue, infer_cell_value};
+use crate::error::ExportError;
+use wasm_bindgen::prelude::*;
+
+/// 可能触发公式执行的首字符
+const FORMULA_TRIGGERS: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+/// 公式注入防护方式
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormulaSanitization {
+    /// 不处理
+    Off,
+    /// 在单元格开头添加单引号 `'`，使其按文本显示（CSV 默认）
+    Prefix,
+    /// 去除开头的危险字符；去除后会改变数字正负的单元格（如 `- 5`）改为添加前缀
+    Strip,
+    /// 拒绝导出，错误码为 `FORMULA_INJECTION`
+    Reject,
+}
+
+/// 判断单元格文本是否可能被当作公式执行
+///
+/// 以危险字符开头但能解析为数字（包括科学计数法）或百分比的文本（如负数），
+/// 以及单独的 `-`（常用于表示空值）视为安全
+///
+/// # 参数
+/// * `text` - 单元格文本
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn is_formula_like(text: &str) -> bool {
+    if !text.starts_with(FORMULA_TRIGGERS) || text == "-" {
+        return false;
+    }
+    !is_number_like(text)
+}
+
+/// 能否解析为数字或百分比，包括 `-2e5` 这样的科学计数法
+fn is_number_like(text: &str) -> bool {
+    if matches!(
+        infer_cell_value(text),
+        CellValue::Number { .. } | CellValue::Percent { .. }
+    ) {
+        return true;
+    }
+
+    // `str::parse` 还接受 `inf`、`NaN` 等写法，只允许数字、符号、小数点和指数
+    let text = text.trim();
+    text.bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'))
+        && text.parse::<f64>().is_ok_and(f64::is_finite)
+}
+
+/// 按 `Strip` 方式处理单元格
+///
+/// 去除的字符中包含负号且剩余部分是数字时（如 `- 5`、`+-5`），去除会改变数值的正负，
+/// 改为添加前缀以保留原文
+fn strip_formula(cell: &mut String) {
+    let stripped = cell.trim_start_matches(FORMULA_TRIGGERS);
+    let removed = &cell[..cell.len() - stripped.len()];
+    if removed.contains('-') && is_number_like(stripped) {
+        cell.insert(0, '\'');
+    } else {
+        *cell = stripped.to_string();
+    }
+}
+
+/// 按防护方式处理一行单元格
+///
+/// # 参数
+/// * `row` - 行数据，原地修改
+/// * `row_index` - 行号（从 0 开始，用于错误信息）
+/// * `mode` - 防护方式
+///
+/// # 返回值
+/// * `Ok(())` - 处理完成
+/// * `Err(ExportError)` - `Reject` 模式下发现可疑单元格，错误码为 `FORMULA_INJECTION`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn sanitize_row(
+    row: &mut [String],
+    row_index: usize,
+    mode: FormulaSanitization,
+) -> Result<(), ExportError> {
+    if mode == FormulaSanitization::Off {
+        return Ok(());
+    }
+
+    for (column, cell) in row.iter_mut().enumerate() {
+        if !is_formula_like(cell) {
+            continue;
+        }
+        match mode {
+            FormulaSanitization::Off => {}
+            FormulaSanitization::Prefix => cell.insert(0, '\''),
+            FormulaSanitization::Strip => strip_formula(cell),
+            FormulaSanitization::Reject => {
+                return Err(ExportError::FormulaInjection {
+                    row: row_index,
+                    column,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -1,76 +1,1052 @@
+use super::ExportFormat;
+use super::file_output::get_document;
+use super::form_controls::cell_text;
+use super::hyperlink::{LinkFormat, link_text, read_cell_link};
+use super::options::ExportOptions;
+use super::type_inference::CellType;
+use crate::error::{ElementKind, ExportError};
+use crate::i18n::Message;
+use std::collections::{HashMap, HashSet};
 use wasm_bindgen::JsCast;
 /// 表格数据提取模块
 ///
-/// 提供从 DOM 中提取表格数据的功能
+/// 提供从 DOM 中提取表格数据的功能，并将 colspan/rowspan 展开为矩形矩阵
 use wasm_bindgen::prelude::*;
-use web_sys::{HtmlTableCellElement, HtmlTableElement, HtmlTableRowElement};
+use web_sys::{Element, HtmlTableCellElement, HtmlTableElement, HtmlTableRowElement, Window};
 
-/// 从 HTML 表格中提取数据
+/// 合并单元格展开方式
+///
+/// 决定被 colspan/rowspan 覆盖的位置在导出结果中如何填充
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpanMode {
+    /// 被覆盖的位置留空（默认）
+    #[default]
+    Blank,
+    /// 被覆盖的位置重复合并单元格的值
+    Repeat,
+}
+
+/// 合并区域
+///
+/// 行列索引均从 0 开始，且包含首尾
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeRange {
+    pub first_row: usize,
+    pub first_col: usize,
+    pub last_row: usize,
+    pub last_col: usize,
+}
+
+impl MergeRange {
+    /// 是否只包含一个单元格（不需要合并）
+    pub fn is_single_cell(&self) -> bool {
+        self.first_row == self.last_row && self.first_col == self.last_col
+    }
+
+    /// 计算列选择后该合并区域在输出中的位置，没有任何列被选中时返回 None
+    fn select_columns(&self, columns: &[usize]) -> Option<SelectedMerge> {
+        let positions: Vec<usize> = columns
+            .iter()
+            .enumerate()
+            .filter(|&(_, &c)| (self.first_col..=self.last_col).contains(&c))
+            .map(|(i, _)| i)
+            .collect();
+        let (&first_col, &last_col) = (positions.iter().min()?, positions.iter().max()?);
+
+        Some(SelectedMerge {
+            anchor: columns.iter().position(|&c| c == self.first_col),
+            first_col,
+            last_col,
+            contiguous: last_col - first_col + 1 == positions.len(),
+        })
+    }
+}
+
+/// 合并区域经过列选择后的位置
+struct SelectedMerge {
+    /// 原左上角所在列在输出中的位置，未被选中时为 None
+    anchor: Option<usize>,
+    /// 输出中最左侧的位置，即新的左上角
+    first_col: usize,
+    last_col: usize,
+    /// 选中的列在输出中是否连续，连续时才保留合并
+    contiguous: bool,
+}
+
+/// 按列选择挑出一行数据
+///
+/// 左上角位于该行的合并区域，其值被移到列选择后的新左上角：原左上角所在列未被选中时复制过去，
+/// 仍被选中但被移到后面时与新左上角交换，避免值出现两次。一次性导出和分批导出共用此规则，
+/// 保证两者输出一致
 ///
 /// # 参数
-/// * `table_id` - HTML 表格元素的 ID
+/// * `row` - 展开合并单元格后的行
+/// * `columns` - 输出列对应的原始列索引，超出行宽的列输出为空字符串
+/// * `anchored` - 左上角位于该行的合并区域
 ///
 /// # 返回值
-/// * `Ok(Vec<Vec<String>>)` - 二维字符串数组，表示表格数据
-/// * `Err(JsValue)` - 提取失败，包含错误信息
-pub fn extract_table_data(table_id: &str) -> Result<Vec<Vec<String>>, JsValue> {
-    // 安全地获取全局的 window 和 document 对象
-    let window = web_sys::window().ok_or_else(|| JsValue::from_str("无法获取 window 对象"))?;
-    let document = window
-        .document()
-        .ok_or_else(|| JsValue::from_str("无法获取 document 对象"))?;
-
-    // 根据 table_id 获取 table 元素，并进行类型检查
-    let table_element = document
-        .get_element_by_id(table_id)
-        .ok_or_else(|| JsValue::from_str(&format!("找不到 ID 为 '{}' 的表格元素", table_id)))?;
+/// 按输出列顺序排列的行数据
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn select_row_columns(
+    row: &[String],
+    columns: &[usize],
+    anchored: &[MergeRange],
+) -> Vec<String> {
+    let mut selected: Vec<String> = columns
+        .iter()
+        .map(|&c| row.get(c).cloned().unwrap_or_default())
+        .collect();
 
-    let table = table_element
-        .dyn_into::<HtmlTableElement>()
-        .map_err(|_| JsValue::from_str(&format!("元素 '{}' 不是有效的 HTML 表格", table_id)))?;
+    for merge in anchored {
+        let Some(target) = merge.select_columns(columns) else {
+            continue;
+        };
+        match target.anchor {
+            Some(anchor) => selected.swap(anchor, target.first_col),
+            None => {
+                selected[target.first_col] = row.get(merge.first_col).cloned().unwrap_or_default()
+            }
+        }
+    }
+    selected
+}
 
-    // 遍历 table 中的每一行
-    let rows = table.rows();
-    let row_count = rows.length();
+/// 合并区域的查找表
+///
+/// 供能够表达合并单元格的导出格式按位置查询跨度
+pub(crate) struct MergeSpans {
+    /// 左上角单元格 -> (跨行数, 跨列数)
+    pub(crate) anchors: HashMap<(usize, usize), (usize, usize)>,
+    /// 被合并区域覆盖的其余单元格
+    pub(crate) covered: HashSet<(usize, usize)>,
+}
 
-    if row_count == 0 {
-        return Err(JsValue::from_str("表格为空，没有数据可导出"));
+impl MergeSpans {
+    pub(crate) fn new(table_data: &TableData) -> Self {
+        let mut anchors = HashMap::new();
+        let mut covered = HashSet::new();
+        for merge in table_data.merges.iter().filter(|m| !m.is_single_cell()) {
+            anchors.insert(
+                (merge.first_row, merge.first_col),
+                (
+                    merge.last_row - merge.first_row + 1,
+                    merge.last_col - merge.first_col + 1,
+                ),
+            );
+            for row in merge.first_row..=merge.last_row {
+                for col in merge.first_col..=merge.last_col {
+                    if (row, col) != (merge.first_row, merge.first_col) {
+                        covered.insert((row, col));
+                    }
+                }
+            }
+        }
+        Self { anchors, covered }
     }
+}
+
+/// 提取后的表格数据
+///
+/// `rows` 是展开合并单元格后的矩形矩阵，`merges` 保留原始的合并几何信息，
+/// 供能够表达合并单元格的导出格式使用，`header_rows` 为开头的表头行数
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableData {
+    pub rows: Vec<Vec<String>>,
+    pub merges: Vec<MergeRange>,
+    pub header_rows: usize,
+    /// 按列的 CSS 水平对齐方式（取自第一行数据），只在导出 Markdown 时读取，未知时为空
+    pub alignments: Vec<Option<ColumnAlign>>,
+    /// 按单元格指定的类型（来自 `data-export-type`），键为 (行, 列)，优先于按列的类型
+    pub cell_types: HashMap<(usize, usize), CellType>,
+    /// 单元格中 `<a href>` 的绝对链接地址，键为 (行, 列)
+    pub links: HashMap<(usize, usize), String>,
+}
 
-    let mut table_data = Vec::new();
+/// 列的水平对齐方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnAlign {
+    Left,
+    Center,
+    Right,
+}
 
-    for i in 0..row_count {
-        let row = rows
+impl ColumnAlign {
+    /// 解析 CSS `text-align` 的计算值，默认值（`start`）等未显式指定的情况返回 `None`
+    pub fn from_css(value: &str) -> Option<Self> {
+        match value.trim() {
+            "left" => Some(ColumnAlign::Left),
+            "center" | "-webkit-center" | "-moz-center" => Some(ColumnAlign::Center),
+            "right" | "end" => Some(ColumnAlign::Right),
+            _ => None,
+        }
+    }
+}
+
+impl TableData {
+    /// 单元格使用的类型：优先取单元格自身指定的类型，其次为按列覆盖的类型
+    ///
+    /// # 参数
+    /// * `row` / `col` - 单元格位置
+    /// * `column_types` - 按列覆盖的单元格类型
+    pub fn cell_type(&self, row: usize, col: usize, column_types: &[CellType]) -> CellType {
+        self.cell_types
+            .get(&(row, col))
+            .copied()
+            .unwrap_or_else(|| column_types.get(col).copied().unwrap_or_default())
+    }
+
+    /// 移除开头的表头行
+    ///
+    /// 跨越表头与数据的合并区域会被截断，其值移动到第一行数据中
+    pub fn remove_header_rows(&mut self) {
+        let header_rows = self.header_rows.min(self.rows.len());
+        let keep: Vec<bool> = (0..self.rows.len()).map(|row| row >= header_rows).collect();
+        self.retain_rows(&keep);
+    }
+
+    /// 只保留指定的行
+    ///
+    /// 表头行数减去被移除的表头行；合并区域收缩到保留的行，
+    /// 左上角所在行被移除时，其值移动到区域内第一个保留的行
+    ///
+    /// # 参数
+    /// * `keep` - 每行是否保留，长度不足时其余行视为保留
+    pub fn retain_rows(&mut self, keep: &[bool]) {
+        let kept = |row: usize| keep.get(row).copied().unwrap_or(true);
+
+        // 每行在结果中的位置（之前保留的行数）
+        let mut positions = Vec::with_capacity(self.rows.len() + 1);
+        let mut count = 0;
+        for row in 0..self.rows.len() {
+            positions.push(count);
+            count += usize::from(kept(row));
+        }
+        positions.push(count);
+
+        let mut merges = Vec::with_capacity(self.merges.len());
+        for merge in std::mem::take(&mut self.merges) {
+            let mut kept_rows = (merge.first_row..=merge.last_row).filter(|&row| kept(row));
+            let Some(first_row) = kept_rows.next() else {
+                continue;
+            };
+            let last_row = kept_rows.next_back().unwrap_or(first_row);
+
+            if first_row != merge.first_row {
+                let from = (merge.first_row, merge.first_col);
+                let to = (first_row, merge.first_col);
+                self.rows[to.0][to.1] = self.rows[from.0][from.1].clone();
+                copy_cell_entry(&mut self.cell_types, from, to);
+                copy_cell_entry(&mut self.links, from, to);
+            }
+            merges.push(MergeRange {
+                first_row: positions[first_row],
+                last_row: positions[last_row],
+                ..merge
+            });
+        }
+
+        self.header_rows = positions[self.header_rows.min(self.rows.len())];
+        self.cell_types = retain_cell_rows(std::mem::take(&mut self.cell_types), kept, &positions);
+        self.links = retain_cell_rows(std::mem::take(&mut self.links), kept, &positions);
+        let mut row = 0;
+        self.rows.retain(|_| {
+            row += 1;
+            kept(row - 1)
+        });
+        self.merges = merges;
+        self.merges.retain(|merge| !merge.is_single_cell());
+    }
+
+    /// 重命名最后一行表头中的列，没有表头行时不做任何处理
+    ///
+    /// # 参数
+    /// * `titles` - 按列的新表头，`None` 表示保留原表头
+    pub fn rename_headers(&mut self, titles: &[Option<String>]) {
+        let Some(header) = self
+            .header_rows
+            .checked_sub(1)
+            .and_then(|row| self.rows.get_mut(row))
+        else {
+            return;
+        };
+        for (cell, title) in header.iter_mut().zip(titles) {
+            if let Some(title) = title {
+                cell.clone_from(title);
+            }
+        }
+    }
+
+    /// 按列索引选择并重排列
+    ///
+    /// 合并区域映射到新的列位置；合并区域内被选中的列在结果中不再连续时，
+    /// 该合并区域被拆开，其值保留在最靠前的位置
+    ///
+    /// # 参数
+    /// * `columns` - 要保留的列索引（从 0 开始），按输出顺序排列
+    ///
+    /// # 返回值
+    /// * `Ok(())` - 选择成功
+    /// * `Err(ExportError)` - 列索引超出范围，错误码为 `COLUMN_OUT_OF_RANGE`
+    pub fn select_columns(&mut self, columns: &[usize]) -> Result<(), ExportError> {
+        let width = self.rows.first().map_or(0, Vec::len);
+        if let Some(&column) = columns.iter().find(|&&c| c >= width) {
+            return Err(ExportError::ColumnOutOfRange { column, width });
+        }
+
+        // 按左上角所在行分组，逐行挑选时把合并区域的值移到新的左上角
+        let mut anchored = vec![Vec::new(); self.rows.len()];
+        for merge in &self.merges {
+            if let Some(row_merges) = anchored.get_mut(merge.first_row) {
+                row_merges.push(*merge);
+            }
+        }
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .zip(&anchored)
+            .map(|(row, row_merges)| select_row_columns(row, columns, row_merges))
+            .collect();
+
+        let mut cell_types = select_cell_columns(&self.cell_types, columns);
+        let mut links = select_cell_columns(&self.links, columns);
+
+        let mut merges = Vec::with_capacity(self.merges.len());
+        for merge in &self.merges {
+            let Some(selected) = merge.select_columns(columns) else {
+                continue;
+            };
+
+            // 类型和链接与值一起移到新的左上角
+            let at = (merge.first_row, selected.first_col);
+            match selected.anchor {
+                Some(anchor) => {
+                    let anchor = (merge.first_row, anchor);
+                    swap_cell_entries(&mut cell_types, anchor, at);
+                    swap_cell_entries(&mut links, anchor, at);
+                }
+                None => {
+                    let from = (merge.first_row, merge.first_col);
+                    set_cell_entry(&mut cell_types, at, self.cell_types.get(&from));
+                    set_cell_entry(&mut links, at, self.links.get(&from));
+                }
+            }
+
+            if selected.contiguous {
+                merges.push(MergeRange {
+                    first_col: selected.first_col,
+                    last_col: selected.last_col,
+                    ..*merge
+                });
+            }
+        }
+
+        if !self.alignments.is_empty() {
+            self.alignments = columns
+                .iter()
+                .map(|&c| self.alignments.get(c).copied().flatten())
+                .collect();
+        }
+
+        self.rows = rows;
+        self.merges = merges;
+        self.merges.retain(|merge| !merge.is_single_cell());
+        self.cell_types = cell_types;
+        self.links = links;
+        Ok(())
+    }
+}
+
+/// 按单元格位置 (行, 列) 存储的附加信息，如单元格类型和链接
+type CellMap<T> = HashMap<(usize, usize), T>;
+
+/// 设置某个位置的附加信息，`value` 为 `None` 时清除
+fn set_cell_entry<T: Clone>(map: &mut CellMap<T>, at: (usize, usize), value: Option<&T>) {
+    match value {
+        Some(value) => map.insert(at, value.clone()),
+        None => map.remove(&at),
+    };
+}
+
+/// 将附加信息从一个位置复制到另一个位置，来源没有时清除目标
+fn copy_cell_entry<T: Clone>(map: &mut CellMap<T>, from: (usize, usize), to: (usize, usize)) {
+    let value = map.get(&from).cloned();
+    set_cell_entry(map, to, value.as_ref());
+}
+
+/// 交换两个位置的附加信息
+fn swap_cell_entries<T>(map: &mut CellMap<T>, a: (usize, usize), b: (usize, usize)) {
+    let value_a = map.remove(&a);
+    let value_b = map.remove(&b);
+    map.extend(value_a.map(|value| (b, value)));
+    map.extend(value_b.map(|value| (a, value)));
+}
+
+/// 只保留指定行的附加信息，并映射到行在结果中的位置
+fn retain_cell_rows<T>(
+    map: CellMap<T>,
+    kept: impl Fn(usize) -> bool,
+    positions: &[usize],
+) -> CellMap<T> {
+    map.into_iter()
+        .filter(|&((row, _), _)| kept(row))
+        .map(|((row, col), value)| ((positions[row], col), value))
+        .collect()
+}
+
+/// 按列选择映射附加信息，同一列被选中多次时每个位置都保留
+fn select_cell_columns<T: Clone>(map: &CellMap<T>, columns: &[usize]) -> CellMap<T> {
+    let mut selected = HashMap::with_capacity(map.len());
+    for (&(row, col), value) in map {
+        for (i, _) in columns.iter().enumerate().filter(|&(_, &c)| c == col) {
+            selected.insert((row, i), value.clone());
+        }
+    }
+    selected
+}
+
+/// 待展开的原始单元格
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawCell {
+    pub text: String,
+    pub colspan: usize,
+    pub rowspan: usize,
+    /// 单元格自身指定的类型，未指定时按列类型处理
+    pub cell_type: Option<CellType>,
+    /// 单元格中的链接地址
+    pub link: Option<String>,
+}
+
+impl RawCell {
+    /// 创建不跨行列的单元格
+    pub fn new(text: impl Into<String>) -> Self {
+        Self::with_span(text, 1, 1)
+    }
+
+    /// 创建带 colspan/rowspan 的单元格
+    ///
+    /// 小于 1 的跨度按 1 处理（包括 HTML 中表示"延伸到分区末尾"的 `rowspan="0"`）
+    pub fn with_span(text: impl Into<String>, colspan: usize, rowspan: usize) -> Self {
+        Self {
+            text: text.into(),
+            colspan: colspan.max(1),
+            rowspan: rowspan.max(1),
+            cell_type: None,
+            link: None,
+        }
+    }
+
+    /// 指定单元格自身的类型
+    pub fn with_type(mut self, cell_type: CellType) -> Self {
+        self.cell_type = Some(cell_type);
+        self
+    }
+
+    /// 指定单元格的链接地址
+    pub fn with_link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+}
+
+/// 某一列上仍被上方 rowspan 占用的状态
+struct PendingSpan {
+    remaining: usize,
+    text: String,
+    cell_type: Option<CellType>,
+    link: Option<String>,
+}
+
+/// 合并单元格网格构建器
+///
+/// 逐行接收原始单元格并展开 colspan/rowspan，跨行状态保存在构建器内部，
+/// 因此既可用于一次性提取，也可用于分批导出时的流式处理
+///
+/// # 注意
+/// 这个类型主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub struct SpanGrid {
+    mode: SpanMode,
+    pending: Vec<Option<PendingSpan>>,
+    merges: Vec<MergeRange>,
+    cell_types: HashMap<(usize, usize), CellType>,
+    links: HashMap<(usize, usize), String>,
+    /// 最近一次 `push_row` 中各单元格展开后的起始列和列数
+    last_row_cells: Vec<(usize, usize)>,
+    row_count: usize,
+    width: usize,
+}
+
+impl SpanGrid {
+    /// 创建新的网格构建器
+    pub fn new(mode: SpanMode) -> Self {
+        Self {
+            mode,
+            pending: Vec::new(),
+            merges: Vec::new(),
+            cell_types: HashMap::new(),
+            links: HashMap::new(),
+            last_row_cells: Vec::new(),
+            row_count: 0,
+            width: 0,
+        }
+    }
+
+    /// 已处理的行数
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    /// 目前为止最宽一行的列数
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// 最近一次 `push_row` 中各单元格（按传入顺序）展开后的起始列和列数
+    ///
+    /// 上方的 rowspan 会把单元格推到右侧，列数可能因遇到被占用的列而截断
+    pub fn last_row_cells(&self) -> &[(usize, usize)] {
+        &self.last_row_cells
+    }
+
+    /// 左上角位于最近一次 `push_row` 所处理行的合并区域
+    ///
+    /// 分批导出逐行选择列时用于移动合并区域的值，见 [`select_row_columns`]
+    pub fn last_row_merges(&self) -> &[MergeRange] {
+        let Some(last_row) = self.row_count.checked_sub(1) else {
+            return &[];
+        };
+        let start = self
+            .merges
+            .iter()
+            .rposition(|merge| merge.first_row != last_row)
+            .map_or(0, |i| i + 1);
+        &self.merges[start..]
+    }
+
+    /// 展开一行原始单元格
+    ///
+    /// colspan 遇到被上方 rowspan 占用的列时在该列之前截断，保证合并区域互不重叠
+    ///
+    /// # 参数
+    /// * `cells` - 该行在 DOM 中的单元格（按文档顺序）
+    ///
+    /// # 返回值
+    /// 展开后的行数据，已包含上方 rowspan 延伸下来的位置
+    pub fn push_row(&mut self, cells: Vec<RawCell>) -> Vec<String> {
+        let row_index = self.row_count;
+        let mut row = Vec::new();
+        let mut col = 0;
+        self.last_row_cells.clear();
+
+        for cell in cells {
+            // 跳过被上方 rowspan 占用的列
+            while self.take_pending(col, &mut row) {
+                col += 1;
+            }
+
+            let colspan = (1..cell.colspan)
+                .find(|&offset| self.is_pending(col + offset))
+                .unwrap_or(cell.colspan);
+            self.last_row_cells.push((col, colspan));
+
+            for offset in 0..colspan {
+                let value = if offset == 0 || self.mode == SpanMode::Repeat {
+                    if let Some(cell_type) = cell.cell_type {
+                        self.cell_types.insert((row_index, col + offset), cell_type);
+                    }
+                    if let Some(ref link) = cell.link {
+                        self.links.insert((row_index, col + offset), link.clone());
+                    }
+                    cell.text.clone()
+                } else {
+                    String::new()
+                };
+                row.push(value);
+
+                if cell.rowspan > 1 {
+                    let target = col + offset;
+                    if self.pending.len() <= target {
+                        self.pending.resize_with(target + 1, || None);
+                    }
+                    self.pending[target] = Some(PendingSpan {
+                        remaining: cell.rowspan - 1,
+                        text: cell.text.clone(),
+                        cell_type: cell.cell_type,
+                        link: cell.link.clone(),
+                    });
+                }
+            }
+
+            if colspan > 1 || cell.rowspan > 1 {
+                self.merges.push(MergeRange {
+                    first_row: row_index,
+                    first_col: col,
+                    last_row: row_index + cell.rowspan - 1,
+                    last_col: col + colspan - 1,
+                });
+            }
+
+            col += colspan;
+        }
+
+        // 行尾仍有上方 rowspan 延伸下来的列时补齐
+        let pending_end = self
+            .pending
+            .iter()
+            .rposition(|p| p.is_some())
+            .map_or(0, |i| i + 1);
+        while col < pending_end {
+            if !self.take_pending(col, &mut row) {
+                row.push(String::new());
+            }
+            col += 1;
+        }
+
+        self.row_count += 1;
+        self.width = self.width.max(row.len());
+        row
+    }
+
+    /// 结束当前的 thead/tbody/tfoot 分区
+    ///
+    /// 与浏览器一致，rowspan 不会跨越分区边界：仍未结束的 rowspan 被截断到已处理的最后一行
+    pub fn end_section(&mut self) {
+        let Some(last_row) = self.row_count.checked_sub(1) else {
+            return;
+        };
+        self.pending.clear();
+        for merge in &mut self.merges {
+            merge.last_row = merge.last_row.min(last_row);
+        }
+        self.merges.retain(|merge| !merge.is_single_cell());
+    }
+
+    /// 完成构建，将已展开的行补齐为矩形并组装为表格数据
+    ///
+    /// # 参数
+    /// * `rows` - 按顺序调用 `push_row` 得到的所有行
+    pub fn into_table_data(mut self, mut rows: Vec<Vec<String>>) -> TableData {
+        let width = self.width;
+        for row in rows.iter_mut() {
+            row.resize(width, String::new());
+        }
+
+        let cell_types = std::mem::take(&mut self.cell_types);
+        let links = std::mem::take(&mut self.links);
+        TableData {
+            rows,
+            merges: self.finish(),
+            header_rows: 0,
+            alignments: Vec::new(),
+            cell_types,
+            links,
+        }
+    }
+
+    /// 完成构建，返回合并区域
+    ///
+    /// 超出表格末尾的 rowspan 会被截断到最后一行
+    pub fn finish(self) -> Vec<MergeRange> {
+        let last_row = self.row_count.saturating_sub(1);
+        self.merges
+            .into_iter()
+            .map(|mut merge| {
+                merge.last_row = merge.last_row.min(last_row);
+                merge
+            })
+            .filter(|merge| !merge.is_single_cell())
+            .collect()
+    }
+
+    /// 该列是否被上方 rowspan 占用
+    fn is_pending(&self, col: usize) -> bool {
+        self.pending.get(col).is_some_and(Option::is_some)
+    }
+
+    /// 如果该列被上方 rowspan 占用，则填充该位置并返回 true
+    fn take_pending(&mut self, col: usize, row: &mut Vec<String>) -> bool {
+        let Some(slot) = self.pending.get_mut(col) else {
+            return false;
+        };
+        let Some(span) = slot.as_mut() else {
+            return false;
+        };
+
+        match self.mode {
+            SpanMode::Repeat => {
+                let position = (self.row_count, row.len());
+                if let Some(cell_type) = span.cell_type {
+                    self.cell_types.insert(position, cell_type);
+                }
+                if let Some(ref link) = span.link {
+                    self.links.insert(position, link.clone());
+                }
+                row.push(span.text.clone());
+            }
+            SpanMode::Blank => row.push(String::new()),
+        }
+
+        span.remaining -= 1;
+        if span.remaining == 0 {
+            *slot = None;
+        }
+        true
+    }
+}
+
+/// 将原始单元格行展开为矩形表格数据
+///
+/// # 参数
+/// * `rows` - 每行的原始单元格
+/// * `mode` - 合并单元格展开方式
+///
+/// # 返回值
+/// 展开后的表格数据，所有行都被补齐到相同列数
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn build_table_data(rows: Vec<Vec<RawCell>>, mode: SpanMode) -> TableData {
+    let mut grid = SpanGrid::new(mode);
+    let expanded: Vec<Vec<String>> = rows.into_iter().map(|row| grid.push_row(row)).collect();
+    grid.into_table_data(expanded)
+}
+
+/// 检测表格的表头行数
+///
+/// 有 `<thead>` 时为其行数；否则首行单元格全部为 `<th>` 时为 1，其余情况为 0
+pub(crate) fn detect_header_rows(table: &HtmlTableElement) -> usize {
+    if let Some(thead) = table.t_head() {
+        return thead.rows().length() as usize;
+    }
+
+    let Some(first_row) = table.rows().get_with_index(0) else {
+        return 0;
+    };
+    let cells = first_row.children();
+    let all_th = cells.length() > 0
+        && (0..cells.length())
+            .filter_map(|i| cells.item(i))
+            .all(|cell| cell.tag_name().eq_ignore_ascii_case("th"));
+
+    usize::from(all_th)
+}
+
+/// 读取第一行数据中各列的 CSS 水平对齐方式
+///
+/// 只读取一行以避免为每个单元格计算样式；读取失败时返回空列表。
+/// 之前的行按与提取相同的规则展开合并单元格，使对齐方式对应单元格展开后所在的列
+///
+/// # 参数
+/// * `table` - 表格元素
+/// * `header_rows` - 表头行数（不计隐藏的行），其后的第一个可见行视为数据行
+/// * `reader` - 行读取器，用于跳过隐藏的行和单元格
+pub(crate) fn read_column_alignments(
+    table: &HtmlTableElement,
+    header_rows: usize,
+    reader: &RowReader,
+) -> Vec<Option<ColumnAlign>> {
+    let Some(window) = web_sys::window() else {
+        return Vec::new();
+    };
+
+    let mut grid = SpanGrid::new(SpanMode::Blank);
+    let mut sections = SectionTracker::new();
+    let mut visible_rows = 0;
+    let rows = table.rows();
+    for i in 0..rows.length() {
+        let Some(row) = rows
             .get_with_index(i)
-            .ok_or_else(|| JsValue::from_str(&format!("无法获取第 {} 行数据", i + 1)))?;
+            .and_then(|row| row.dyn_into::<HtmlTableRowElement>().ok())
+        else {
+            return Vec::new();
+        };
+
+        // 只需要跨度，不读取单元格文本
+        let cells = row.cells();
+        let cells: Vec<HtmlTableCellElement> = (0..cells.length())
+            .filter_map(|j| {
+                cells
+                    .get_with_index(j)?
+                    .dyn_into::<HtmlTableCellElement>()
+                    .ok()
+            })
+            .filter(|cell| !reader.is_hidden(cell))
+            .collect();
+        sections.enter(&row, &mut grid);
+        grid.push_row(
+            cells
+                .iter()
+                .map(|cell| {
+                    RawCell::with_span("", cell.col_span() as usize, cell.row_span() as usize)
+                })
+                .collect(),
+        );
+
+        if reader.is_row_hidden(&row) {
+            continue;
+        }
+        if visible_rows < header_rows {
+            visible_rows += 1;
+            continue;
+        }
+
+        let mut alignments = vec![None; grid.width()];
+        for (cell, &(first_col, colspan)) in cells.iter().zip(grid.last_row_cells()) {
+            let align = window
+                .get_computed_style(cell)
+                .ok()
+                .flatten()
+                .and_then(|style| style.get_property_value("text-align").ok())
+                .and_then(|value| ColumnAlign::from_css(&value));
+            alignments[first_col..first_col + colspan].fill(align);
+        }
+        return alignments;
+    }
+    Vec::new()
+}
+
+/// 读取单元格的文本、跨度、类型和链接
+///
+/// 优先使用 `data-export-value` 属性作为导出值，其次为单元格中表单控件的当前值，最后为显示的文本；
+/// `data-export-type` 属性（`text`、`number`、`boolean`、`date`）指定该单元格的类型，
+/// 优先于复选框的布尔类型
+///
+/// # 参数
+/// * `cell` - 单元格元素
+/// * `link_format` - 带链接单元格的文本输出方式，为 `None` 时不读取链接
+fn read_raw_cell(cell: &HtmlTableCellElement, link_format: Option<LinkFormat>) -> RawCell {
+    let (text, control_type) = match cell.get_attribute("data-export-value") {
+        Some(text) => (text, None),
+        None => cell_text(cell),
+    };
+    let link = link_format.and_then(|_| read_cell_link(cell));
+    let text = match (&link, link_format) {
+        (Some(url), Some(link_format)) => link_text(&text, url, link_format),
+        _ => text,
+    };
 
-        let row = row
-            .dyn_into::<HtmlTableRowElement>()
-            .map_err(|_| JsValue::from_str(&format!("第 {} 行不是有效的表格行", i + 1)))?;
+    let mut raw_cell = RawCell::with_span(text, cell.col_span() as usize, cell.row_span() as usize);
+    raw_cell.cell_type = cell
+        .get_attribute("data-export-type")
+        .and_then(|name| CellType::from_name(&name))
+        .or(control_type);
+    raw_cell.link = link;
+    raw_cell
+}
+
+/// DOM 表格行读取器
+///
+/// 按导出配置读取行中的单元格，一次性提取和分批导出共用
+pub(crate) struct RowReader {
+    /// 跳过隐藏的行和单元格时用于读取计算样式
+    window: Option<Window>,
+    /// 带链接单元格的文本输出方式；只有 XLSX（写入超链接）和输出链接地址的 CSV/TSV
+    /// 需要读取链接，其余情况为 `None`，跳过查询
+    link_format: Option<LinkFormat>,
+}
+
+impl RowReader {
+    /// 根据导出配置创建读取器
+    pub(crate) fn new(options: &ExportOptions) -> Result<Self, ExportError> {
+        let window = if options.skip_hidden {
+            Some(
+                web_sys::window()
+                    .ok_or_else(|| ExportError::browser_api(Message::WindowUnavailable))?,
+            )
+        } else {
+            None
+        };
+        let link_format = match options.format {
+            ExportFormat::Xlsx => Some(LinkFormat::Text),
+            ExportFormat::Csv | ExportFormat::Tsv if options.link_format != LinkFormat::Text => {
+                Some(options.link_format)
+            }
+            _ => None,
+        };
+        Ok(Self {
+            window,
+            link_format,
+        })
+    }
+
+    /// 元素是否对用户隐藏；未开启 `skip_hidden` 时总是返回 false
+    ///
+    /// 检查 `hidden` 属性、`aria-hidden="true"` 以及计算样式 `display: none`、`visibility: collapse`
+    pub(crate) fn is_hidden(&self, element: &Element) -> bool {
+        let Some(ref window) = self.window else {
+            return false;
+        };
+        if element.has_attribute("hidden")
+            || element.get_attribute("aria-hidden").as_deref() == Some("true")
+        {
+            return true;
+        }
 
-        let mut row_data = Vec::new();
+        let Some(style) = window.get_computed_style(element).ok().flatten() else {
+            return false;
+        };
+        style
+            .get_property_value("display")
+            .is_ok_and(|v| v == "none")
+            || style
+                .get_property_value("visibility")
+                .is_ok_and(|v| v == "collapse")
+    }
+
+    /// 行或其所在的 thead/tbody/tfoot 是否被隐藏
+    pub(crate) fn is_row_hidden(&self, row: &HtmlTableRowElement) -> bool {
+        if self.window.is_none() {
+            return false;
+        }
+        self.is_hidden(row)
+            || row
+                .parent_element()
+                .filter(|parent| !parent.tag_name().eq_ignore_ascii_case("table"))
+                .is_some_and(|section| self.is_hidden(&section))
+    }
+
+    /// 读取一行中的所有原始单元格，跳过隐藏的单元格
+    ///
+    /// 隐藏的单元格不占据位置，与浏览器的渲染结果一致，因此被列选择器隐藏的列整列消失
+    ///
+    /// # 参数
+    /// * `row` - 表格行元素
+    /// * `row_index` - 行索引（从 0 开始，仅用于错误信息）
+    pub(crate) fn read_row(
+        &self,
+        row: &HtmlTableRowElement,
+        row_index: usize,
+    ) -> Result<Vec<RawCell>, ExportError> {
         let cells = row.cells();
         let cell_count = cells.length();
+        let mut raw_cells = Vec::with_capacity(cell_count as usize);
 
         for j in 0..cell_count {
-            let cell = cells.get_with_index(j).ok_or_else(|| {
-                JsValue::from_str(&format!("无法获取第 {} 行第 {} 列单元格", i + 1, j + 1))
-            })?;
+            let read_failed = || ExportError::ReadFailed {
+                row: row_index,
+                column: Some(j as usize),
+            };
 
-            let cell = cell.dyn_into::<HtmlTableCellElement>().map_err(|_| {
-                JsValue::from_str(&format!(
-                    "第 {} 行第 {} 列不是有效的表格单元格",
-                    i + 1,
-                    j + 1
-                ))
-            })?;
+            let cell = cells.get_with_index(j).ok_or_else(read_failed)?;
+            let cell = cell
+                .dyn_into::<HtmlTableCellElement>()
+                .map_err(|_| read_failed())?;
 
-            let cell_text = cell.inner_text();
-            row_data.push(cell_text);
+            if !self.is_hidden(&cell) {
+                raw_cells.push(read_raw_cell(&cell, self.link_format));
+            }
         }
 
-        table_data.push(row_data);
+        Ok(raw_cells)
+    }
+}
+
+/// 行所在分区的跟踪器
+///
+/// 行的父元素（thead/tbody/tfoot）与上一行不同时结束网格中的当前分区，
+/// 使 rowspan 不跨越分区边界
+pub(crate) struct SectionTracker {
+    parent: Option<Element>,
+}
+
+impl SectionTracker {
+    pub(crate) fn new() -> Self {
+        Self { parent: None }
+    }
+
+    /// 在展开一行之前调用，行进入新的分区时结束 `grid` 中的上一个分区
+    pub(crate) fn enter(&mut self, row: &HtmlTableRowElement, grid: &mut SpanGrid) {
+        let parent = row.parent_element();
+        let same_section = match (&parent, &self.parent) {
+            (Some(parent), Some(last)) => parent.is_same_node(Some(last)),
+            _ => false,
+        };
+        if !same_section {
+            grid.end_section();
+        }
+        self.parent = parent;
+    }
+}
+
+/// 根据 ID 查找表格元素，并进行类型检查
+///
+/// # 参数
+/// * `table_id` - HTML 表格元素的 ID
+pub(crate) fn find_table(table_id: &str) -> Result<HtmlTableElement, ExportError> {
+    if table_id.is_empty() {
+        return Err(ExportError::invalid_argument(Message::TableIdEmpty));
+    }
+
+    get_document()?
+        .get_element_by_id(table_id)
+        .ok_or_else(|| ExportError::TableNotFound {
+            table_id: table_id.to_string(),
+        })?
+        .dyn_into::<HtmlTableElement>()
+        .map_err(|_| ExportError::InvalidElement {
+            element_id: table_id.to_string(),
+            expected: ElementKind::Table,
+        })
+}
+
+/// 从 HTML 表格中提取数据
+///
+/// # 参数
+/// * `table_id` - HTML 表格元素的 ID
+/// * `options` - 导出配置，使用其中的 `span_mode` 和 `skip_hidden`
+///
+/// # 返回值
+/// * `Ok(TableData)` - 展开合并单元格后的表格数据
+/// * `Err(ExportError)` - 提取失败，包含错误码和上下文
+pub fn extract_table_data(
+    table_id: &str,
+    options: &ExportOptions,
+) -> Result<TableData, ExportError> {
+    let table = find_table(table_id)?;
+    let reader = RowReader::new(options)?;
+
+    // 遍历 table 中的每一行
+    let rows = table.rows();
+    let row_count = rows.length();
+
+    if row_count == 0 {
+        return Err(ExportError::EmptyTable);
+    }
+
+    let mut grid = SpanGrid::new(options.span_mode);
+    let mut sections = SectionTracker::new();
+    let mut expanded = Vec::with_capacity(row_count as usize);
+    let mut keep = Vec::with_capacity(row_count as usize);
+
+    for i in 0..row_count {
+        let row = rows
+            .get_with_index(i)
+            .and_then(|row| row.dyn_into::<HtmlTableRowElement>().ok())
+            .ok_or(ExportError::ReadFailed {
+                row: i as usize,
+                column: None,
+            })?;
+
+        // 隐藏的行也参与展开，保持其后各行的 rowspan 位置正确
+        sections.enter(&row, &mut grid);
+        expanded.push(grid.push_row(reader.read_row(&row, i as usize)?));
+        keep.push(!reader.is_row_hidden(&row));
     }
 
+    let mut table_data = grid.into_table_data(expanded);
+    table_data.header_rows = detect_header_rows(&table);
+    table_data.retain_rows(&keep);
+    if table_data.rows.is_empty() {
+        return Err(ExportError::EmptyTable);
+    }
+    // 只有 Markdown 使用对齐方式，其他格式不为大表格计算样式
+    if options.format == ExportFormat::Markdown {
+        table_data.alignments = read_column_alignments(&table, table_data.header_rows, &reader);
+    }
     Ok(table_data)
 }
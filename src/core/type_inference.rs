@@ -0,0 +1,334 @@
+/// 单元格类型推断模块
+///
+/// 从单元格文本推断数字、百分比、布尔值和日期，供 Excel 导出写入带类型的单元格
+use wasm_bindgen::prelude::*;
+
+/// 列类型
+///
+/// 用于按列覆盖自动类型推断，例如让 "00123" 这样的编号列保持文本
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellType {
+    /// 自动推断（默认）
+    #[default]
+    Auto,
+    /// 始终作为文本
+    Text,
+    /// 作为数字（包括百分比），无法解析时回退为文本
+    Number,
+    /// 作为布尔值，无法解析时回退为文本
+    Boolean,
+    /// 作为日期或日期时间，无法解析时回退为文本
+    Date,
+}
+
+impl CellType {
+    /// 按名称解析类型（不区分大小写），用于 `data-export-type` 等字符串配置
+    ///
+    /// # 参数
+    /// * `name` - `auto`、`text`、`number`、`boolean` 或 `date`
+    ///
+    /// # 返回值
+    /// 无法识别的名称返回 `None`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(CellType::Auto),
+            "text" => Some(CellType::Text),
+            "number" => Some(CellType::Number),
+            "boolean" => Some(CellType::Boolean),
+            "date" => Some(CellType::Date),
+            _ => None,
+        }
+    }
+}
+
+/// 推断出的单元格值
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellValue {
+    /// 文本，按原样写入
+    Text,
+    /// 数字，`decimals` 为小数位数，`grouped` 表示原文本带千位分隔符
+    Number {
+        value: f64,
+        decimals: u8,
+        grouped: bool,
+    },
+    /// 百分比，`value` 为比例值（"12.5%" 对应 0.125）
+    Percent { value: f64, decimals: u8 },
+    /// 布尔值
+    Boolean(bool),
+    /// 日期
+    Date { year: u16, month: u8, day: u8 },
+    /// 日期时间
+    DateTime {
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u16,
+        minute: u8,
+        second: f64,
+    },
+}
+
+impl CellValue {
+    /// 该值在 Excel 中使用的数字格式
+    ///
+    /// # 返回值
+    /// * `Some(String)` - Excel 数字格式字符串
+    /// * `None` - 使用常规格式
+    pub fn num_format(&self) -> Option<String> {
+        match *self {
+            CellValue::Text | CellValue::Boolean(_) => None,
+            CellValue::Number {
+                decimals, grouped, ..
+            } => {
+                if !grouped && decimals == 0 {
+                    return None;
+                }
+                let integer = if grouped { "#,##0" } else { "0" };
+                Some(format!("{}{}", integer, decimal_suffix(decimals)))
+            }
+            CellValue::Percent { decimals, .. } => Some(format!("0{}%", decimal_suffix(decimals))),
+            CellValue::Date { .. } => Some("yyyy-mm-dd".to_string()),
+            CellValue::DateTime { .. } => Some("yyyy-mm-dd hh:mm:ss".to_string()),
+        }
+    }
+}
+
+/// 根据列类型解析单元格值
+///
+/// # 参数
+/// * `text` - 单元格文本
+/// * `cell_type` - 列类型，`Auto` 表示自动推断
+///
+/// # 返回值
+/// 解析后的单元格值，无法按指定类型解析时返回 `CellValue::Text`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn resolve_cell_value(text: &str, cell_type: CellType) -> CellValue {
+    let trimmed = text.trim();
+    let parsed = match cell_type {
+        CellType::Auto => return infer_cell_value(text),
+        CellType::Text => None,
+        CellType::Number => parse_percent(trimmed).or_else(|| parse_number(trimmed, true)),
+        CellType::Boolean => parse_boolean(trimmed),
+        CellType::Date => parse_date(trimmed),
+    };
+    parsed.unwrap_or(CellValue::Text)
+}
+
+/// 自动推断单元格值
+///
+/// 识别整数、小数（可带千位分隔符）、百分比、布尔值（true/false）以及 ISO 日期和日期时间。
+/// 以 0 开头的多位整数（如 "00123"）和超过 15 位有效数字的数字会保留为文本，
+/// 以免丢失前导零或精度
+///
+/// # 参数
+/// * `text` - 单元格文本
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn infer_cell_value(text: &str) -> CellValue {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return CellValue::Text;
+    }
+
+    parse_boolean(trimmed)
+        .or_else(|| parse_percent(trimmed))
+        .or_else(|| parse_number(trimmed, false))
+        .or_else(|| parse_date(trimmed))
+        .unwrap_or(CellValue::Text)
+}
+
+fn decimal_suffix(decimals: u8) -> String {
+    if decimals == 0 {
+        String::new()
+    } else {
+        format!(".{}", "0".repeat(decimals as usize))
+    }
+}
+
+fn parse_boolean(text: &str) -> Option<CellValue> {
+    if text.eq_ignore_ascii_case("true") {
+        Some(CellValue::Boolean(true))
+    } else if text.eq_ignore_ascii_case("false") {
+        Some(CellValue::Boolean(false))
+    } else {
+        None
+    }
+}
+
+fn parse_percent(text: &str) -> Option<CellValue> {
+    let number = text.strip_suffix('%')?.trim_end();
+    match parse_number(number, true)? {
+        CellValue::Number {
+            value, decimals, ..
+        } => Some(CellValue::Percent {
+            value: value / 100.0,
+            decimals,
+        }),
+        _ => None,
+    }
+}
+
+/// 解析十进制数字
+///
+/// `allow_leading_zero` 为 false 时，"0123" 这类可能是编号的文本不会被识别为数字
+fn parse_number(text: &str, allow_leading_zero: bool) -> Option<CellValue> {
+    let unsigned = text
+        .strip_prefix('-')
+        .or_else(|| text.strip_prefix('+'))
+        .unwrap_or(text);
+
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    if let Some(fraction) = fraction
+        && (fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return None;
+    }
+
+    let grouped = integer.contains(',');
+    let digits: String = if grouped {
+        let mut groups = integer.split(',');
+        let first = groups.next()?;
+        if first.is_empty() || first.len() > 3 || first.starts_with('0') {
+            return None;
+        }
+        let mut digits = first.to_string();
+        for group in groups {
+            if group.len() != 3 {
+                return None;
+            }
+            digits.push_str(group);
+        }
+        digits
+    } else {
+        integer.to_string()
+    };
+
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    if !allow_leading_zero && digits.len() > 1 && digits.starts_with('0') {
+        return None;
+    }
+
+    // 超过 15 位有效数字会在 Excel 中丢失精度（如身份证号、银行卡号）
+    let fraction_digits = fraction.unwrap_or("");
+    let significant = format!("{}{}", digits.trim_start_matches('0'), fraction_digits);
+    if significant.trim_end_matches('0').len() > 15 {
+        return None;
+    }
+
+    let normalized = format!(
+        "{}{}.{}",
+        if text.starts_with('-') { "-" } else { "" },
+        digits,
+        if fraction_digits.is_empty() {
+            "0"
+        } else {
+            fraction_digits
+        }
+    );
+    let value = normalized.parse::<f64>().ok()?;
+
+    Some(CellValue::Number {
+        value,
+        decimals: fraction_digits.len().min(u8::MAX as usize) as u8,
+        grouped,
+    })
+}
+
+/// 解析 ISO 格式的日期（YYYY-MM-DD）或日期时间（YYYY-MM-DD[T ]HH:MM[:SS[.fff]][Z]）
+fn parse_date(text: &str) -> Option<CellValue> {
+    let (date_part, time_part) = match text.find(['T', ' ']) {
+        Some(index) => (&text[..index], Some(&text[index + 1..])),
+        None => (text, None),
+    };
+
+    let (year, month, day) = parse_ymd(date_part)?;
+
+    let Some(time_part) = time_part else {
+        return Some(CellValue::Date { year, month, day });
+    };
+
+    let time_part = time_part.strip_suffix('Z').unwrap_or(time_part);
+    let mut fields = time_part.split(':');
+    let hour = parse_fixed_digits(fields.next()?, 2)?;
+    let minute = parse_fixed_digits(fields.next()?, 2)?;
+    let second = match fields.next() {
+        Some(second) => {
+            let (whole, fraction) = match second.split_once('.') {
+                Some((whole, fraction)) => (whole, Some(fraction)),
+                None => (second, None),
+            };
+            let whole = parse_fixed_digits(whole, 2)?;
+            let fraction = match fraction {
+                Some(f) if !f.is_empty() && f.bytes().all(|b| b.is_ascii_digit()) => {
+                    format!("0.{}", f).parse::<f64>().ok()?
+                }
+                Some(_) => return None,
+                None => 0.0,
+            };
+            whole as f64 + fraction
+        }
+        None => 0.0,
+    };
+
+    if fields.next().is_some() || hour > 23 || minute > 59 || second >= 60.0 {
+        return None;
+    }
+
+    Some(CellValue::DateTime {
+        year,
+        month,
+        day,
+        hour: hour as u16,
+        minute: minute as u8,
+        second,
+    })
+}
+
+fn parse_ymd(text: &str) -> Option<(u16, u8, u8)> {
+    let mut fields = text.split('-');
+    let year = parse_fixed_digits(fields.next()?, 4)?;
+    let month = parse_fixed_digits(fields.next()?, 2)?;
+    let day = parse_fixed_digits(fields.next()?, 2)?;
+    if fields.next().is_some() {
+        return None;
+    }
+
+    // Excel 的日期序列从 1900 年开始
+    if !(1900..=9999).contains(&year) || !(1..=12).contains(&month) {
+        return None;
+    }
+
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let days_in_month = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if day == 0 || day > days_in_month {
+        return None;
+    }
+
+    Some((year as u16, month as u8, day as u8))
+}
+
+fn parse_fixed_digits(text: &str, len: usize) -> Option<u32> {
+    if text.len() != len || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
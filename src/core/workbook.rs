@@ -0,0 +1,70 @@
+/// 多工作表工作簿模块
+///
+/// 解析工作表描述列表，从 DOM 表格或 JavaScript 数据中提取每个工作表的数据
+use super::data_extractor::extract_js_data;
+use super::options::ExportOptions;
+use super::table_extractor::{TableData, extract_table_data};
+use crate::error::ExportError;
+use crate::i18n::Message;
+use js_sys::{Array, Reflect};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+
+/// 提取工作簿中每个工作表的名称和数据
+///
+/// # 参数
+/// * `sheets` - 工作表描述数组，元素为 `{ sheet_name?, table_id }` 或 `{ sheet_name?, data, columns? }`；
+///   未提供 `sheet_name` 时依次命名为 `Sheet1`、`Sheet2`……
+/// * `options` - 导出配置，从 DOM 表格提取时使用其中的 `span_mode` 和 `skip_hidden`
+///
+/// # 返回值
+/// * `Ok(Vec<(String, TableData)>)` - 工作表名称与数据，顺序与输入一致
+/// * `Err(ExportError)` - 提取失败，包含错误码和上下文
+pub fn extract_workbook_sheets(
+    sheets: &Array,
+    options: &ExportOptions,
+) -> Result<Vec<(String, TableData)>, ExportError> {
+    if sheets.length() == 0 {
+        return Err(ExportError::invalid_argument(Message::SheetListEmpty));
+    }
+
+    sheets
+        .iter()
+        .enumerate()
+        .map(|(i, sheet)| {
+            if !sheet.is_object() {
+                return Err(ExportError::invalid_argument(Message::SheetNotObject {
+                    index: i,
+                }));
+            }
+
+            let sheet_name =
+                get_string(&sheet, "sheet_name").unwrap_or_else(|| format!("Sheet{}", i + 1));
+
+            let table_data = if let Some(table_id) = get_string(&sheet, "table_id") {
+                extract_table_data(&table_id, options)?
+            } else {
+                let data = get_property(&sheet, "data");
+                if data.is_undefined() {
+                    return Err(ExportError::invalid_argument(Message::SheetSourceMissing {
+                        sheet_name,
+                    }));
+                }
+                let columns = get_property(&sheet, "columns").dyn_into::<Array>().ok();
+                extract_js_data(&data, columns)?
+            };
+
+            Ok((sheet_name, table_data))
+        })
+        .collect()
+}
+
+/// 读取对象的属性，读取失败时视为 `undefined`
+fn get_property(object: &JsValue, key: &str) -> JsValue {
+    Reflect::get(object, &JsValue::from_str(key)).unwrap_or(JsValue::UNDEFINED)
+}
+
+/// 读取对象的字符串属性
+fn get_string(object: &JsValue, key: &str) -> Option<String> {
+    get_property(object, key).as_string()
+}
@@ -0,0 +1,212 @@
+/// 错误类型模块
+///
+/// 提供带稳定错误码的导出错误类型。传递到 JavaScript 时转换为 `Error` 对象，
+/// 其 `code` 属性为错误码，并附带行号、列号等上下文字段，便于调用方按错误类型分支处理
+use crate::i18n::{Locale, Message, error_message, get_locale};
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+/// 期望的元素类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElementKind {
+    /// `<table>` 元素
+    Table,
+    /// `<tbody>` 等表格分区元素
+    Tbody,
+}
+
+/// 导出错误
+///
+/// # 示例
+/// ```javascript
+/// try {
+///     export_table('my-table', '报表');
+/// } catch (e) {
+///     switch (e.code) {
+///         case 'TABLE_NOT_FOUND': alert(`找不到表格 ${e.table_id}`); break;
+///         case 'INVALID_FILENAME': alert('文件名不合法'); break;
+///         default: console.error(e.message);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportError {
+    /// 参数不合法（如空的表格 ID、为 0 的批次大小）
+    InvalidArgument { message: Message },
+    /// 找不到指定 ID 的表格元素
+    TableNotFound { table_id: String },
+    /// 找不到指定 ID 的 tbody 元素
+    TbodyNotFound { tbody_id: String },
+    /// 元素类型不符合要求
+    InvalidElement {
+        element_id: String,
+        expected: ElementKind,
+    },
+    /// 表格或数据为空
+    EmptyTable,
+    /// 无法读取表格中的行或单元格（行号、列号从 0 开始）
+    ReadFailed { row: usize, column: Option<usize> },
+    /// JavaScript 数据格式不正确（行号从 0 开始）
+    InvalidData {
+        row: Option<usize>,
+        message: Message,
+    },
+    /// 单元格内容可能被当作公式执行，按配置拒绝导出（行号、列号为导出结果中的位置，从 0 开始）
+    FormulaInjection { row: usize, column: usize },
+    /// 列索引超出范围
+    ColumnOutOfRange { column: usize, width: usize },
+    /// 找不到列配置中按表头文本引用的列
+    ColumnNotFound { header: String },
+    /// 文件名不合法
+    InvalidFilename { filename: String, reason: Message },
+    /// 工作表名称不合法或重复
+    InvalidSheetName { sheet_name: String, reason: Message },
+    /// 写入 CSV 数据失败（行号从 0 开始）
+    CsvWriteFailed { row: Option<usize>, message: String },
+    /// 写入 JSON 数据失败
+    JsonWriteFailed { message: String },
+    /// 文本包含目标编码无法表示的字符
+    EncodingFailed {
+        encoding: &'static str,
+        character: char,
+    },
+    /// 写入 Excel 数据失败（行号、列号从 0 开始）
+    XlsxWriteFailed {
+        row: Option<usize>,
+        column: Option<usize>,
+        message: String,
+    },
+    /// 写入 ODS 数据失败
+    OdsWriteFailed { message: String },
+    /// 创建 Blob、File 或下载链接失败
+    BlobCreationFailed { message: Message },
+    /// 浏览器环境不可用（如无法获取 window/document）
+    BrowserApi { message: Message },
+    /// 导出被 `AbortSignal` 取消
+    Cancelled,
+}
+
+impl ExportError {
+    /// 稳定的错误码，可在 JavaScript 中通过 `error.code` 读取
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidArgument { .. } => "INVALID_ARGUMENT",
+            Self::TableNotFound { .. } => "TABLE_NOT_FOUND",
+            Self::TbodyNotFound { .. } => "TBODY_NOT_FOUND",
+            Self::InvalidElement { .. } => "INVALID_ELEMENT",
+            Self::EmptyTable => "EMPTY_TABLE",
+            Self::ReadFailed { .. } => "READ_FAILED",
+            Self::InvalidData { .. } => "INVALID_DATA",
+            Self::FormulaInjection { .. } => "FORMULA_INJECTION",
+            Self::ColumnOutOfRange { .. } => "COLUMN_OUT_OF_RANGE",
+            Self::ColumnNotFound { .. } => "COLUMN_NOT_FOUND",
+            Self::InvalidFilename { .. } => "INVALID_FILENAME",
+            Self::InvalidSheetName { .. } => "INVALID_SHEET_NAME",
+            Self::CsvWriteFailed { .. } => "CSV_WRITE_FAILED",
+            Self::JsonWriteFailed { .. } => "JSON_WRITE_FAILED",
+            Self::EncodingFailed { .. } => "ENCODING_FAILED",
+            Self::XlsxWriteFailed { .. } => "XLSX_WRITE_FAILED",
+            Self::OdsWriteFailed { .. } => "ODS_WRITE_FAILED",
+            Self::BlobCreationFailed { .. } => "BLOB_CREATION_FAILED",
+            Self::BrowserApi { .. } => "BROWSER_API_ERROR",
+            Self::Cancelled => "CANCELLED",
+        }
+    }
+
+    /// 按指定语言格式化错误信息
+    pub fn message(&self, locale: Locale) -> String {
+        error_message(self, locale)
+    }
+
+    /// 创建参数错误
+    pub(crate) fn invalid_argument(message: Message) -> Self {
+        Self::InvalidArgument { message }
+    }
+
+    /// 创建数据格式错误
+    pub(crate) fn invalid_data(row: Option<usize>, message: Message) -> Self {
+        Self::InvalidData { row, message }
+    }
+
+    /// 创建浏览器环境错误
+    pub(crate) fn browser_api(message: Message) -> Self {
+        Self::BrowserApi { message }
+    }
+
+    /// 转换为 JavaScript `Error` 对象，错误信息使用指定语言
+    ///
+    /// `name` 为 `"ExportError"`（取消时为 `"AbortError"`，与 `fetch` 等标准 API 一致），
+    /// `code` 为错误码，其余上下文字段按错误类型附加
+    pub(crate) fn into_js(self, locale: Locale) -> JsValue {
+        let js_error = js_sys::Error::new(&self.message(locale));
+        js_error.set_name(match self {
+            ExportError::Cancelled => "AbortError",
+            _ => "ExportError",
+        });
+
+        let _ = js_sys::Reflect::set(&js_error, &"code".into(), &self.code().into());
+        for (key, value) in self.context() {
+            let _ = js_sys::Reflect::set(&js_error, &key.into(), &value);
+        }
+
+        js_error.into()
+    }
+
+    /// 附加到 JavaScript 错误对象上的上下文字段
+    fn context(&self) -> Vec<(&'static str, JsValue)> {
+        match self {
+            Self::TableNotFound { table_id } => vec![("table_id", table_id.into())],
+            Self::TbodyNotFound { tbody_id } => vec![("tbody_id", tbody_id.into())],
+            Self::InvalidElement { element_id, .. } => vec![("element_id", element_id.into())],
+            Self::ReadFailed { row, column } => index_fields(Some(*row), *column),
+            Self::FormulaInjection { row, column } => index_fields(Some(*row), Some(*column)),
+            Self::InvalidData { row, .. } | Self::CsvWriteFailed { row, .. } => {
+                index_fields(*row, None)
+            }
+            Self::XlsxWriteFailed { row, column, .. } => index_fields(*row, *column),
+            Self::ColumnOutOfRange { column, width } => vec![
+                ("column", (*column as f64).into()),
+                ("width", (*width as f64).into()),
+            ],
+            Self::ColumnNotFound { header } => vec![("header", header.into())],
+            Self::InvalidFilename { filename, .. } => vec![("filename", filename.into())],
+            Self::InvalidSheetName { sheet_name, .. } => vec![("sheet_name", sheet_name.into())],
+            Self::EncodingFailed {
+                encoding,
+                character,
+            } => vec![
+                ("encoding", (*encoding).into()),
+                ("character", character.to_string().into()),
+            ],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// 行号、列号上下文字段
+fn index_fields(row: Option<usize>, column: Option<usize>) -> Vec<(&'static str, JsValue)> {
+    let mut fields = Vec::new();
+    if let Some(row) = row {
+        fields.push(("row", (row as f64).into()));
+    }
+    if let Some(column) = column {
+        fields.push(("column", (column as f64).into()));
+    }
+    fields
+}
+
+impl fmt::Display for ExportError {
+    /// 使用当前全局语言格式化错误信息
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message(get_locale()))
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<ExportError> for JsValue {
+    /// 转换为 JavaScript `Error` 对象，错误信息使用当前全局语言
+    fn from(error: ExportError) -> Self {
+        error.into_js(get_locale())
+    }
+}
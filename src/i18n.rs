@@ -0,0 +1,562 @@
+/// 国际化模块
+///
+/// 提供错误信息与默认文件名的多语言消息目录。语言可以通过 `set_locale` 全局设置，
+/// 也可以通过 `ExportOptions.locale` 按次覆盖
+use crate::error::{ElementKind, ExportError};
+use std::cell::Cell;
+use wasm_bindgen::prelude::*;
+
+/// 消息语言
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 简体中文（默认）
+    #[default]
+    Zh,
+    /// 英语
+    En,
+    /// 德语
+    De,
+    /// 日语
+    Ja,
+}
+
+impl Locale {
+    /// 根据 BCP 47 语言标签（如 `"en-US"`、`"ja"`）匹配语言，不支持的语言返回 `None`
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "zh" => Some(Locale::Zh),
+            "en" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            "ja" => Some(Locale::Ja),
+            _ => None,
+        }
+    }
+
+    /// 未指定文件名时使用的默认文件名（不含扩展名）
+    pub fn default_file_stem(self) -> &'static str {
+        match self {
+            Locale::Zh | Locale::En => "table_export",
+            Locale::De => "tabellen_export",
+            Locale::Ja => "テーブル出力",
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT_LOCALE: Cell<Locale> = const { Cell::new(Locale::Zh) };
+}
+
+/// 设置全局消息语言
+///
+/// 影响之后所有导出的错误信息和默认文件名；`ExportOptions.locale` 可以按次覆盖
+///
+/// # 参数
+/// * `locale` - 消息语言
+///
+/// # 示例
+/// ```javascript
+/// import init, { set_locale, Locale } from './pkg/excel_exporter.js';
+///
+/// await init();
+/// set_locale(Locale.En);
+/// ```
+#[wasm_bindgen]
+pub fn set_locale(locale: Locale) {
+    CURRENT_LOCALE.with(|current| current.set(locale));
+}
+
+/// 获取当前的全局消息语言
+#[wasm_bindgen]
+pub fn get_locale() -> Locale {
+    CURRENT_LOCALE.with(Cell::get)
+}
+
+/// 根据语言标签设置全局消息语言
+///
+/// # 参数
+/// * `tag` - BCP 47 语言标签（如 `navigator.language` 返回的 `"en-US"`）
+///
+/// # 返回值
+/// * `true` - 已切换到对应语言
+/// * `false` - 不支持该语言，保持当前语言不变
+///
+/// # 示例
+/// ```javascript
+/// set_locale_from_tag(navigator.language);
+/// ```
+#[wasm_bindgen]
+pub fn set_locale_from_tag(tag: &str) -> bool {
+    match Locale::from_tag(tag) {
+        Some(locale) => {
+            set_locale(locale);
+            true
+        }
+        None => false,
+    }
+}
+
+/// 按语言选择消息模板并格式化
+///
+/// 每种语言的模板必须使用全部参数（可以通过 `{0}`、`{1}` 调整顺序）
+macro_rules! tr {
+    ($locale:expr, zh: $zh:literal, en: $en:literal, de: $de:literal, ja: $ja:literal $(, $arg:expr)* $(,)?) => {
+        match $locale {
+            Locale::Zh => format!($zh $(, $arg)*),
+            Locale::En => format!($en $(, $arg)*),
+            Locale::De => format!($de $(, $arg)*),
+            Locale::Ja => format!($ja $(, $arg)*),
+        }
+    };
+}
+
+/// 可本地化的消息
+///
+/// 错误中除错误码以外的说明性内容，在转换为文本时才按语言格式化
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    TableIdEmpty,
+    BatchSizeZero,
+    NotTextFormat {
+        format: &'static str,
+    },
+    NotSingleAsciiChar {
+        option: &'static str,
+        value: String,
+    },
+    PrefixUnsupported {
+        format: &'static str,
+    },
+    HtmlOptionUnsupported {
+        option: &'static str,
+    },
+    ColumnListNotArray {
+        option: &'static str,
+    },
+    ColumnEntryInvalid {
+        option: &'static str,
+        index: usize,
+    },
+    ColumnOptionsConflict,
+    SheetListEmpty,
+    SheetNotObject {
+        index: usize,
+    },
+    SheetSourceMissing {
+        sheet_name: String,
+    },
+    DataNotArray,
+    ObjectRowsNeedColumns,
+    RowReadFailed {
+        row: usize,
+        detail: String,
+    },
+    RowNotArrayOrObject {
+        row: usize,
+    },
+    ColumnKeyMissing {
+        index: usize,
+    },
+    WindowUnavailable,
+    DocumentUnavailable,
+    AnchorCreationFailed {
+        detail: String,
+    },
+    AnchorInvalid,
+    YieldFailed {
+        detail: String,
+    },
+    BlobFailed {
+        detail: String,
+    },
+    FileFailed {
+        detail: String,
+    },
+    ObjectUrlFailed {
+        detail: String,
+    },
+    ClipboardUnavailable,
+    ClipboardWriteFailed {
+        detail: String,
+    },
+    FilenameEmpty,
+    FilenamePathSeparator,
+    FilenameIllegalChar(char),
+    FilenameTooLong,
+    FilenameReserved(String),
+    FilenameDotOrSpace,
+    SheetNameEmpty,
+    SheetNameTooLong {
+        sheet_name: String,
+    },
+    SheetNameIllegalChar {
+        sheet_name: String,
+        ch: char,
+    },
+    SheetNameApostrophe {
+        sheet_name: String,
+    },
+    SheetNameDuplicate {
+        sheet_name: String,
+    },
+    /// 第三方库返回的原始信息，不做翻译
+    Detail(String),
+}
+
+impl Message {
+    /// 按指定语言格式化消息（行号、序号从 1 开始显示）
+    pub fn localize(&self, locale: Locale) -> String {
+        match self {
+            Message::TableIdEmpty => tr!(locale,
+                zh: "表格 ID 不能为空",
+                en: "Table ID must not be empty",
+                de: "Die Tabellen-ID darf nicht leer sein",
+                ja: "テーブル ID を空にすることはできません"),
+            Message::BatchSizeZero => tr!(locale,
+                zh: "批次大小必须大于 0",
+                en: "Batch size must be greater than 0",
+                de: "Die Batchgröße muss größer als 0 sein",
+                ja: "バッチサイズは 0 より大きくする必要があります"),
+            Message::NotTextFormat { format } => tr!(locale,
+                zh: "{0} 格式不是文本格式，无法导出为字符串",
+                en: "The {0} format is not a text format and cannot be exported as a string",
+                de: "Das Format {0} ist kein Textformat und kann nicht als Zeichenkette exportiert werden",
+                ja: "{0} 形式はテキスト形式ではないため、文字列としてエクスポートできません",
+                format),
+            Message::NotSingleAsciiChar { option, value } => tr!(locale,
+                zh: "{0} 必须是单个 ASCII 字符: '{1}'",
+                en: "{0} must be a single ASCII character: '{1}'",
+                de: "{0} muss ein einzelnes ASCII-Zeichen sein: '{1}'",
+                ja: "{0} は 1 文字の ASCII 文字である必要があります: '{1}'",
+                option, value),
+            Message::PrefixUnsupported { format } => tr!(locale,
+                zh: "{0} 格式不支持 Prefix 公式注入防护，请使用 Strip 或 Reject",
+                en: "The {0} format does not support Prefix formula sanitization, use Strip or Reject",
+                de: "Das Format {0} unterstützt die Formelbereinigung Prefix nicht, verwenden Sie Strip oder Reject",
+                ja: "{0} 形式は Prefix による数式インジェクション対策に対応していません。Strip または Reject を使用してください",
+                format),
+            Message::HtmlOptionUnsupported { option } => tr!(locale,
+                zh: "HTML 快照保留原表格的结构，不支持 {0} 选项",
+                en: "HTML snapshots keep the original table structure and do not support the {0} option",
+                de: "HTML-Schnappschüsse behalten die ursprüngliche Tabellenstruktur bei und unterstützen die Option {0} nicht",
+                ja: "HTML スナップショットは元の表の構造を保持するため、{0} オプションには対応していません",
+                option),
+            Message::ColumnListNotArray { option } => tr!(locale,
+                zh: "{0} 必须是数组",
+                en: "{0} must be an array",
+                de: "{0} muss ein Array sein",
+                ja: "{0} は配列である必要があります",
+                option),
+            Message::ColumnEntryInvalid { option, index } => tr!(locale,
+                zh: "{0} 的第 {1} 项必须是列索引、表头文本或 {{ column, title }} 对象",
+                en: "Entry {1} of {0} must be a column index, header text or {{ column, title }} object",
+                de: "Eintrag {1} von {0} muss ein Spaltenindex, ein Kopfzeilentext oder ein {{ column, title }}-Objekt sein",
+                ja: "{0} の {1} 番目の要素は列インデックス、ヘッダーテキスト、または {{ column, title }} オブジェクトである必要があります",
+                option, index + 1),
+            Message::ColumnOptionsConflict => tr!(locale,
+                zh: "columns 和 column_spec 不能同时设置",
+                en: "columns and column_spec cannot both be set",
+                de: "columns und column_spec können nicht gleichzeitig gesetzt werden",
+                ja: "columns と column_spec は同時に設定できません"),
+            Message::SheetListEmpty => tr!(locale,
+                zh: "工作表列表不能为空",
+                en: "The sheet list must not be empty",
+                de: "Die Liste der Tabellenblätter darf nicht leer sein",
+                ja: "シートの一覧を空にすることはできません"),
+            Message::SheetNotObject { index } => tr!(locale,
+                zh: "第 {0} 个工作表描述必须是对象",
+                en: "Sheet descriptor {0} must be an object",
+                de: "Tabellenblatt-Beschreibung {0} muss ein Objekt sein",
+                ja: "{0} 番目のシート定義はオブジェクトである必要があります",
+                index + 1),
+            Message::SheetSourceMissing { sheet_name } => tr!(locale,
+                zh: "工作表 '{0}' 必须提供 table_id 或 data",
+                en: "Sheet '{0}' must provide either table_id or data",
+                de: "Tabellenblatt '{0}' muss table_id oder data angeben",
+                ja: "シート '{0}' には table_id または data を指定する必要があります",
+                sheet_name),
+            Message::DataNotArray => tr!(locale,
+                zh: "导出数据必须是数组",
+                en: "Export data must be an array",
+                de: "Die Exportdaten müssen ein Array sein",
+                ja: "エクスポートするデータは配列である必要があります"),
+            Message::ObjectRowsNeedColumns => tr!(locale,
+                zh: "对象数组需要提供列定义",
+                en: "Column definitions are required for an array of objects",
+                de: "Für ein Array von Objekten sind Spaltendefinitionen erforderlich",
+                ja: "オブジェクトの配列には列定義が必要です"),
+            Message::RowReadFailed { row, detail } => tr!(locale,
+                zh: "读取第 {0} 行数据失败: {1}",
+                en: "Failed to read data row {0}: {1}",
+                de: "Datenzeile {0} konnte nicht gelesen werden: {1}",
+                ja: "{0} 行目のデータの読み取りに失敗しました: {1}",
+                row + 1, detail),
+            Message::RowNotArrayOrObject { row } => tr!(locale,
+                zh: "第 {0} 行数据必须是数组或对象",
+                en: "Data row {0} must be an array or an object",
+                de: "Datenzeile {0} muss ein Array oder ein Objekt sein",
+                ja: "{0} 行目のデータは配列またはオブジェクトである必要があります",
+                row + 1),
+            Message::ColumnKeyMissing { index } => tr!(locale,
+                zh: "第 {0} 个列定义缺少 key 字段",
+                en: "Column definition {0} is missing the key field",
+                de: "Der Spaltendefinition {0} fehlt das Feld key",
+                ja: "{0} 番目の列定義に key フィールドがありません",
+                index + 1),
+            Message::WindowUnavailable => tr!(locale,
+                zh: "无法获取 window 对象",
+                en: "Unable to access the window object",
+                de: "Auf das window-Objekt kann nicht zugegriffen werden",
+                ja: "window オブジェクトを取得できません"),
+            Message::DocumentUnavailable => tr!(locale,
+                zh: "无法获取 document 对象",
+                en: "Unable to access the document object",
+                de: "Auf das document-Objekt kann nicht zugegriffen werden",
+                ja: "document オブジェクトを取得できません"),
+            Message::AnchorCreationFailed { detail } => tr!(locale,
+                zh: "创建下载链接元素失败: {0}",
+                en: "Failed to create the download link element: {0}",
+                de: "Das Download-Link-Element konnte nicht erstellt werden: {0}",
+                ja: "ダウンロードリンク要素の作成に失敗しました: {0}",
+                detail),
+            Message::AnchorInvalid => tr!(locale,
+                zh: "创建的元素不是有效的锚点元素",
+                en: "The created element is not a valid anchor element",
+                de: "Das erstellte Element ist kein gültiges Anker-Element",
+                ja: "作成された要素は有効なアンカー要素ではありません"),
+            Message::YieldFailed { detail } => tr!(locale,
+                zh: "让出控制权失败: {0}",
+                en: "Failed to yield to the browser: {0}",
+                de: "Die Kontrolle konnte nicht an den Browser abgegeben werden: {0}",
+                ja: "ブラウザへの制御の受け渡しに失敗しました: {0}",
+                detail),
+            Message::BlobFailed { detail } => tr!(locale,
+                zh: "创建 Blob 对象失败: {0}",
+                en: "Failed to create the Blob: {0}",
+                de: "Blob konnte nicht erstellt werden: {0}",
+                ja: "Blob の作成に失敗しました: {0}",
+                detail),
+            Message::FileFailed { detail } => tr!(locale,
+                zh: "创建 File 对象失败: {0}",
+                en: "Failed to create the File: {0}",
+                de: "File konnte nicht erstellt werden: {0}",
+                ja: "File の作成に失敗しました: {0}",
+                detail),
+            Message::ObjectUrlFailed { detail } => tr!(locale,
+                zh: "创建下载链接失败: {0}",
+                en: "Failed to create the download URL: {0}",
+                de: "Die Download-URL konnte nicht erstellt werden: {0}",
+                ja: "ダウンロード URL の作成に失敗しました: {0}",
+                detail),
+            Message::ClipboardUnavailable => tr!(locale,
+                zh: "剪贴板不可用（需要在 HTTPS 等安全上下文中使用）",
+                en: "The clipboard is unavailable (a secure context such as HTTPS is required)",
+                de: "Die Zwischenablage ist nicht verfügbar (ein sicherer Kontext wie HTTPS ist erforderlich)",
+                ja: "クリップボードを利用できません（HTTPS などのセキュアコンテキストが必要です）"),
+            Message::ClipboardWriteFailed { detail } => tr!(locale,
+                zh: "写入剪贴板失败: {0}",
+                en: "Failed to write to the clipboard: {0}",
+                de: "Schreiben in die Zwischenablage fehlgeschlagen: {0}",
+                ja: "クリップボードへの書き込みに失敗しました: {0}",
+                detail),
+            Message::FilenameEmpty => tr!(locale,
+                zh: "文件名不能为空",
+                en: "Filename must not be empty",
+                de: "Der Dateiname darf nicht leer sein",
+                ja: "ファイル名を空にすることはできません"),
+            Message::FilenamePathSeparator => tr!(locale,
+                zh: "文件名不能包含路径分隔符",
+                en: "Filename must not contain path separators",
+                de: "Der Dateiname darf keine Pfadtrennzeichen enthalten",
+                ja: "ファイル名にパス区切り文字を含めることはできません"),
+            Message::FilenameIllegalChar(ch) => tr!(locale,
+                zh: "文件名不能包含非法字符: {0}",
+                en: "Filename must not contain the character: {0}",
+                de: "Der Dateiname darf folgendes Zeichen nicht enthalten: {0}",
+                ja: "ファイル名に使用できない文字が含まれています: {0}",
+                ch),
+            Message::FilenameTooLong => tr!(locale,
+                zh: "文件名过长（最大 255 个字符）",
+                en: "Filename is too long (maximum 255 characters)",
+                de: "Der Dateiname ist zu lang (maximal 255 Zeichen)",
+                ja: "ファイル名が長すぎます（最大 255 文字）"),
+            Message::FilenameReserved(base_name) => tr!(locale,
+                zh: "文件名 '{0}' 是系统保留名称",
+                en: "'{0}' is a reserved system name",
+                de: "'{0}' ist ein reservierter Systemname",
+                ja: "'{0}' はシステムの予約名です",
+                base_name),
+            Message::FilenameDotOrSpace => tr!(locale,
+                zh: "文件名不能以点或空格开头或结尾",
+                en: "Filename must not start or end with a dot or space",
+                de: "Der Dateiname darf nicht mit einem Punkt oder Leerzeichen beginnen oder enden",
+                ja: "ファイル名の先頭または末尾にドットや空白を使用することはできません"),
+            Message::SheetNameEmpty => tr!(locale,
+                zh: "工作表名称不能为空",
+                en: "Sheet name must not be empty",
+                de: "Der Tabellenblattname darf nicht leer sein",
+                ja: "シート名を空にすることはできません"),
+            Message::SheetNameTooLong { sheet_name } => tr!(locale,
+                zh: "工作表名称 '{0}' 过长（最多 31 个字符）",
+                en: "Sheet name '{0}' is too long (maximum 31 characters)",
+                de: "Der Tabellenblattname '{0}' ist zu lang (maximal 31 Zeichen)",
+                ja: "シート名 '{0}' が長すぎます（最大 31 文字）",
+                sheet_name),
+            Message::SheetNameIllegalChar { sheet_name, ch } => tr!(locale,
+                zh: "工作表名称 '{0}' 不能包含非法字符: {1}",
+                en: "Sheet name '{0}' must not contain the character: {1}",
+                de: "Der Tabellenblattname '{0}' darf folgendes Zeichen nicht enthalten: {1}",
+                ja: "シート名 '{0}' に使用できない文字が含まれています: {1}",
+                sheet_name, ch),
+            Message::SheetNameApostrophe { sheet_name } => tr!(locale,
+                zh: "工作表名称 '{0}' 不能以单引号开头或结尾",
+                en: "Sheet name '{0}' must not start or end with an apostrophe",
+                de: "Der Tabellenblattname '{0}' darf nicht mit einem Apostroph beginnen oder enden",
+                ja: "シート名 '{0}' の先頭または末尾にアポストロフィを使用することはできません",
+                sheet_name),
+            Message::SheetNameDuplicate { sheet_name } => tr!(locale,
+                zh: "工作表名称 '{0}' 重复",
+                en: "Sheet name '{0}' is used more than once",
+                de: "Der Tabellenblattname '{0}' wird mehrfach verwendet",
+                ja: "シート名 '{0}' が重複しています",
+                sheet_name),
+            Message::Detail(detail) => detail.clone(),
+        }
+    }
+}
+
+/// 按指定语言格式化元素类型名称
+fn element_kind_name(kind: ElementKind, locale: Locale) -> String {
+    match kind {
+        ElementKind::Table => tr!(locale,
+            zh: "HTML 表格",
+            en: "HTML table",
+            de: "HTML-Tabelle",
+            ja: "HTML テーブル"),
+        ElementKind::Tbody => tr!(locale,
+            zh: "HTML 表格部分(tbody)",
+            en: "HTML table section (tbody)",
+            de: "HTML-Tabellenabschnitt (tbody)",
+            ja: "HTML テーブルセクション (tbody)"),
+    }
+}
+
+/// 按指定语言格式化导出错误（行号、列号从 1 开始显示）
+pub(crate) fn error_message(error: &ExportError, locale: Locale) -> String {
+    match error {
+        ExportError::InvalidArgument { message }
+        | ExportError::InvalidData { message, .. }
+        | ExportError::BlobCreationFailed { message }
+        | ExportError::BrowserApi { message } => message.localize(locale),
+        ExportError::TableNotFound { table_id } => tr!(locale,
+            zh: "找不到 ID 为 '{0}' 的表格元素",
+            en: "Table element with ID '{0}' not found",
+            de: "Tabellenelement mit der ID '{0}' wurde nicht gefunden",
+            ja: "ID '{0}' のテーブル要素が見つかりません",
+            table_id),
+        ExportError::TbodyNotFound { tbody_id } => tr!(locale,
+            zh: "找不到 ID 为 '{0}' 的 tbody 元素",
+            en: "tbody element with ID '{0}' not found",
+            de: "tbody-Element mit der ID '{0}' wurde nicht gefunden",
+            ja: "ID '{0}' の tbody 要素が見つかりません",
+            tbody_id),
+        ExportError::InvalidElement {
+            element_id,
+            expected,
+        } => tr!(locale,
+            zh: "元素 '{0}' 不是有效的 {1}",
+            en: "Element '{0}' is not a valid {1}",
+            de: "Element '{0}' ist nicht vom Typ {1}",
+            ja: "要素 '{0}' は有効な {1} ではありません",
+            element_id, element_kind_name(*expected, locale)),
+        ExportError::EmptyTable => tr!(locale,
+            zh: "表格为空，没有数据可导出",
+            en: "The table is empty; there is no data to export",
+            de: "Die Tabelle ist leer, es gibt keine Daten zum Exportieren",
+            ja: "テーブルが空のため、エクスポートするデータがありません"),
+        ExportError::ReadFailed { row, column: None } => tr!(locale,
+            zh: "无法读取第 {0} 行数据",
+            en: "Failed to read row {0}",
+            de: "Zeile {0} konnte nicht gelesen werden",
+            ja: "{0} 行目のデータを読み取れません",
+            row + 1),
+        ExportError::ReadFailed {
+            row,
+            column: Some(column),
+        } => tr!(locale,
+            zh: "无法读取第 {0} 行第 {1} 列单元格",
+            en: "Failed to read the cell at row {0}, column {1}",
+            de: "Die Zelle in Zeile {0}, Spalte {1} konnte nicht gelesen werden",
+            ja: "{0} 行目 {1} 列目のセルを読み取れません",
+            row + 1, column + 1),
+        ExportError::FormulaInjection { row, column } => tr!(locale,
+            zh: "第 {0} 行第 {1} 列的内容以公式字符开头，已拒绝导出",
+            en: "The cell at row {0}, column {1} starts with a formula character and was rejected",
+            de: "Die Zelle in Zeile {0}, Spalte {1} beginnt mit einem Formelzeichen und wurde abgelehnt",
+            ja: "{0} 行目 {1} 列目のセルは数式の文字で始まるため、エクスポートを拒否しました",
+            row + 1, column + 1),
+        ExportError::ColumnOutOfRange { column, width } => tr!(locale,
+            zh: "列索引 {0} 超出范围（共 {1} 列）",
+            en: "Column index {0} is out of range (the table has {1} columns)",
+            de: "Spaltenindex {0} liegt außerhalb des gültigen Bereichs (die Tabelle hat {1} Spalten)",
+            ja: "列インデックス {0} が範囲外です（全 {1} 列）",
+            column, width),
+        ExportError::ColumnNotFound { header } => tr!(locale,
+            zh: "找不到表头为 '{0}' 的列",
+            en: "No column with header '{0}' found",
+            de: "Keine Spalte mit der Überschrift '{0}' gefunden",
+            ja: "ヘッダーが '{0}' の列が見つかりません",
+            header),
+        ExportError::InvalidFilename { reason, .. } => tr!(locale,
+            zh: "文件名验证失败: {0}",
+            en: "Invalid filename: {0}",
+            de: "Ungültiger Dateiname: {0}",
+            ja: "ファイル名が無効です: {0}",
+            reason.localize(locale)),
+        ExportError::InvalidSheetName { reason, .. } => tr!(locale,
+            zh: "工作表名称无效: {0}",
+            en: "Invalid sheet name: {0}",
+            de: "Ungültiger Tabellenblattname: {0}",
+            ja: "シート名が無効です: {0}",
+            reason.localize(locale)),
+        ExportError::CsvWriteFailed { message, .. } => tr!(locale,
+            zh: "写入 CSV 数据失败: {0}",
+            en: "Failed to write CSV data: {0}",
+            de: "CSV-Daten konnten nicht geschrieben werden: {0}",
+            ja: "CSV データの書き込みに失敗しました: {0}",
+            message),
+        ExportError::JsonWriteFailed { message } => tr!(locale,
+            zh: "写入 JSON 数据失败: {0}",
+            en: "Failed to write JSON data: {0}",
+            de: "JSON-Daten konnten nicht geschrieben werden: {0}",
+            ja: "JSON データの書き込みに失敗しました: {0}",
+            message),
+        ExportError::EncodingFailed {
+            encoding,
+            character,
+        } => tr!(locale,
+            zh: "字符 '{1}' 无法使用 {0} 编码表示",
+            en: "Character '{1}' cannot be represented in the {0} encoding",
+            de: "Das Zeichen '{1}' kann in der Kodierung {0} nicht dargestellt werden",
+            ja: "文字 '{1}' は {0} エンコーディングで表現できません",
+            encoding, character),
+        ExportError::XlsxWriteFailed { message, .. } => tr!(locale,
+            zh: "写入 Excel 数据失败: {0}",
+            en: "Failed to write Excel data: {0}",
+            de: "Excel-Daten konnten nicht geschrieben werden: {0}",
+            ja: "Excel データの書き込みに失敗しました: {0}",
+            message),
+        ExportError::OdsWriteFailed { message } => tr!(locale,
+            zh: "写入 ODS 数据失败: {0}",
+            en: "Failed to write ODS data: {0}",
+            de: "ODS-Daten konnten nicht geschrieben werden: {0}",
+            ja: "ODS データの書き込みに失敗しました: {0}",
+            message),
+        ExportError::Cancelled => tr!(locale,
+            zh: "导出已取消",
+            en: "Export cancelled",
+            de: "Export abgebrochen",
+            ja: "エクスポートはキャンセルされました"),
+    }
+}
@@ -1,5 +1,8 @@
 mod batch_export;
+mod clipboard;
 mod core;
+mod error;
+mod i18n;
 mod resource;
 mod utils;
 mod validation;
@@ -10,14 +13,45 @@ mod validation;
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 // 重新导出所有公共 API
-pub use validation::{ensure_extension, validate_filename};
+pub use error::{ElementKind, ExportError};
+pub use i18n::{Locale, Message, get_locale, set_locale, set_locale_from_tag};
 pub use resource::UrlGuard;
+pub use validation::{
+    ensure_extension, validate_filename, validate_sheet_name, validate_sheet_names,
+};
+
+// 旧版签名的文件名验证，保留到下一个主版本
+#[allow(deprecated)]
+pub use validation::validate_filename_legacy;
 
 // 导出新的统一接口
-pub use core::{export_table, ExportFormat};
+pub use core::{
+    CellType, CsvEncoding, CsvQuoteStyle, ExportFormat, ExportOptions, FormulaSanitization,
+    JsonLayout, LineTerminator, LinkFormat, SpanMode, export_data, export_data_with_options,
+    export_table, export_table_to_blob, export_table_to_blob_with_options, export_table_to_bytes,
+    export_table_to_bytes_with_options, export_table_to_string, export_table_with_options,
+    export_workbook,
+};
+
+// 表格数据模型与合并单元格展开（主要供内部使用，但也导出以便测试）
+#[doc(hidden)]
+pub use core::{
+    CellValue, ColumnAlign, ColumnRef, ColumnSelection, ColumnSpec, FormValue, HtmlCell, HtmlRow,
+    HtmlSection, HtmlSectionKind, HtmlTable, MergeRange, RawCell, SpanGrid, TableData,
+    build_clipboard_content, build_csv_bytes, build_html_bytes, build_json_bytes,
+    build_markdown_bytes, build_ods_bytes, build_table_data, build_xlsx_bytes, encode_text,
+    escape_cell, extract_js_data, form_cell_value, format_number, infer_cell_value,
+    is_exportable_url, is_formula_like, link_text, resolve_cell_value, resolve_columns,
+    sanitize_row, select_row_columns,
+};
 
 // 导出分批异步导出
-pub use batch_export::export_table_to_csv_batch;
+#[doc(hidden)]
+pub use batch_export::export_table_batch_to_bytes;
+pub use batch_export::{export_table_batch_with_options, export_table_to_csv_batch};
+
+// 导出剪贴板复制
+pub use clipboard::copy_table_to_clipboard;
 
 // 导出 utils 模块的公共函数
 pub use utils::set_panic_hook;
@@ -1,7 +1,9 @@
 /// 文件名验证模块
 ///
 /// 提供安全的文件名验证功能，防止路径遍历和非法文件名攻击
-///
+use crate::error::ExportError;
+use crate::i18n::{Locale, Message};
+
 /// 验证文件名是否安全合法
 ///
 /// # 参数
@@ -9,33 +11,61 @@
 ///
 /// # 返回值
 /// * `Ok(())` - 文件名合法
-/// * `Err(String)` - 文件名不合法，包含错误信息
+/// * `Err(ExportError)` - 文件名不合法，错误码为 `INVALID_FILENAME`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn validate_filename(filename: &str) -> Result<(), ExportError> {
+    check_filename(filename).map_err(|reason| ExportError::InvalidFilename {
+        filename: filename.to_string(),
+        reason,
+    })
+}
+
+/// 检查文件名，返回不合法的原因
+/// 验证文件名是否安全合法（旧版签名）
+///
+/// # 参数
+/// * `filename` - 要验证的文件名
+///
+/// # 返回值
+/// * `Ok(())` - 文件名合法
+/// * `Err(String)` - 文件名不合法，包含中文错误信息
 ///
 /// # 注意
 /// 这个函数主要供内部使用，但也导出以便测试
 #[doc(hidden)]
-pub fn validate_filename(filename: &str) -> Result<(), String> {
+#[deprecated(
+    since = "1.1.0",
+    note = "请使用 validate_filename，它返回带有错误码的 ExportError"
+)]
+pub fn validate_filename_legacy(filename: &str) -> Result<(), String> {
+    check_filename(filename).map_err(|reason| reason.localize(Locale::Zh))
+}
+
+fn check_filename(filename: &str) -> Result<(), Message> {
     // 检查文件名是否为空
     if filename.is_empty() {
-        return Err("文件名不能为空".to_string());
+        return Err(Message::FilenameEmpty);
     }
 
     // 检查文件名中的危险字符（路径分隔符）
     if filename.contains('/') || filename.contains('\\') {
-        return Err("文件名不能包含路径分隔符".to_string());
+        return Err(Message::FilenamePathSeparator);
     }
 
     // 检查其他危险字符
     let dangerous_chars = ['<', '>', ':', '"', '|', '?', '*'];
     for ch in dangerous_chars.iter() {
         if filename.contains(*ch) {
-            return Err(format!("文件名不能包含非法字符: {}", ch));
+            return Err(Message::FilenameIllegalChar(*ch));
         }
     }
 
     // 检查文件名长度（大多数文件系统限制为 255 字节）
     if filename.len() > 255 {
-        return Err("文件名过长（最大 255 个字符）".to_string());
+        return Err(Message::FilenameTooLong);
     }
 
     // 检查 Windows 保留文件名
@@ -46,7 +76,7 @@ pub fn validate_filename(filename: &str) -> Result<(), String> {
     ];
 
     if reserved_names.contains(&base_name.to_uppercase().as_str()) {
-        return Err(format!("文件名 '{}' 是系统保留名称", base_name));
+        return Err(Message::FilenameReserved(base_name.to_string()));
     }
 
     // 检查文件名是否以点或空格开头/结尾（Windows 不支持）
@@ -55,12 +85,91 @@ pub fn validate_filename(filename: &str) -> Result<(), String> {
         || filename.ends_with('.')
         || filename.ends_with(' ')
     {
-        return Err("文件名不能以点或空格开头或结尾".to_string());
+        return Err(Message::FilenameDotOrSpace);
     }
 
     Ok(())
 }
 
+/// 验证 Excel 工作表名称是否合法
+///
+/// # 参数
+/// * `name` - 要验证的工作表名称
+///
+/// # 返回值
+/// * `Ok(())` - 名称合法
+/// * `Err(ExportError)` - 名称不合法，错误码为 `INVALID_SHEET_NAME`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn validate_sheet_name(name: &str) -> Result<(), ExportError> {
+    check_sheet_name(name).map_err(|reason| ExportError::InvalidSheetName {
+        sheet_name: name.to_string(),
+        reason,
+    })
+}
+
+/// 检查工作表名称，返回不合法的原因
+fn check_sheet_name(name: &str) -> Result<(), Message> {
+    if name.is_empty() {
+        return Err(Message::SheetNameEmpty);
+    }
+
+    // Excel 按字符计算长度，最多 31 个
+    if name.chars().count() > 31 {
+        return Err(Message::SheetNameTooLong {
+            sheet_name: name.to_string(),
+        });
+    }
+
+    let forbidden_chars = ['[', ']', ':', '*', '?', '/', '\\'];
+    if let Some(ch) = name.chars().find(|ch| forbidden_chars.contains(ch)) {
+        return Err(Message::SheetNameIllegalChar {
+            sheet_name: name.to_string(),
+            ch,
+        });
+    }
+
+    if name.starts_with('\'') || name.ends_with('\'') {
+        return Err(Message::SheetNameApostrophe {
+            sheet_name: name.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// 验证一组工作表名称是否合法且互不重复
+///
+/// Excel 比较工作表名称时不区分大小写
+///
+/// # 参数
+/// * `names` - 工作簿中所有工作表的名称
+///
+/// # 返回值
+/// * `Ok(())` - 所有名称合法且唯一
+/// * `Err(ExportError)` - 存在不合法或重复的名称，错误码为 `INVALID_SHEET_NAME`
+///
+/// # 注意
+/// 这个函数主要供内部使用，但也导出以便测试
+#[doc(hidden)]
+pub fn validate_sheet_names(names: &[&str]) -> Result<(), ExportError> {
+    let mut seen = std::collections::HashSet::with_capacity(names.len());
+    for name in names {
+        validate_sheet_name(name)?;
+        if !seen.insert(name.to_lowercase()) {
+            return Err(ExportError::InvalidSheetName {
+                sheet_name: name.to_string(),
+                reason: Message::SheetNameDuplicate {
+                    sheet_name: name.to_string(),
+                },
+            });
+        }
+    }
+    Ok(())
+}
+
 /// 确保文件名有正确的扩展名
 ///
 /// # 参数
@@ -0,0 +1,45 @@
+//! 集成测试共用的辅助函数
+//!
+//! 每个测试文件只使用其中一部分，未使用的函数在对应的测试中不产生警告
+
+#![allow(dead_code)]
+
+use belobog_stellar_grid::{RawCell, SpanMode, TableData, build_table_data};
+use std::io::{Cursor, Read};
+use zip::ZipArchive;
+
+/// 由单元格文本构造表格数据
+///
+/// # 参数
+/// * `rows` - 每行的单元格文本
+/// * `header_rows` - 开头的表头行数
+pub fn table(rows: &[&[&str]], header_rows: usize) -> TableData {
+    let rows = rows
+        .iter()
+        .map(|row| row.iter().map(|text| RawCell::new(*text)).collect())
+        .collect();
+    table_from_cells(rows, header_rows)
+}
+
+/// 由单元格（可以带有合并、类型和链接）构造表格数据，合并单元格按 Blank 方式展开
+///
+/// # 参数
+/// * `rows` - 每行的单元格
+/// * `header_rows` - 开头的表头行数
+pub fn table_from_cells(rows: Vec<Vec<RawCell>>, header_rows: usize) -> TableData {
+    let mut table_data = build_table_data(rows, SpanMode::Blank);
+    table_data.header_rows = header_rows;
+    table_data
+}
+
+/// 读取 zip 包（XLSX/ODS）中指定文件的文本内容
+pub fn read_entry(bytes: &[u8], name: &str) -> String {
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut content = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut content)
+        .unwrap();
+    content
+}
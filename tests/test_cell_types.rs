@@ -0,0 +1,122 @@
+//! 单元格类型覆盖测试
+//!
+//! 测试 `data-export-type` 对应的单元格类型在合并单元格展开、行过滤和列选择中的保留，
+//! 以及其相对按列类型的优先级
+
+use belobog_stellar_grid::{
+    CellType, ExportFormat, ExportOptions, RawCell, SpanMode, build_json_bytes, build_table_data,
+};
+
+#[test]
+fn test_cell_type_from_name() {
+    assert_eq!(CellType::from_name("number"), Some(CellType::Number));
+    assert_eq!(CellType::from_name(" Date "), Some(CellType::Date));
+    assert_eq!(CellType::from_name("TEXT"), Some(CellType::Text));
+    assert_eq!(CellType::from_name("boolean"), Some(CellType::Boolean));
+    assert_eq!(CellType::from_name("auto"), Some(CellType::Auto));
+    assert_eq!(CellType::from_name("currency"), None);
+}
+
+#[test]
+fn test_cell_type_overrides_column_type() {
+    let data = build_table_data(
+        vec![vec![
+            RawCell::new("00123").with_type(CellType::Number),
+            RawCell::new("1200"),
+        ]],
+        SpanMode::Blank,
+    );
+
+    let column_types = [CellType::Text, CellType::Text];
+    assert_eq!(data.cell_type(0, 0, &column_types), CellType::Number);
+    assert_eq!(data.cell_type(0, 1, &column_types), CellType::Text);
+    assert_eq!(data.cell_type(0, 1, &[]), CellType::Auto);
+}
+
+#[test]
+fn test_span_modes_propagate_cell_type() {
+    let rows = || {
+        vec![
+            vec![
+                RawCell::with_span("1234.5", 2, 2).with_type(CellType::Number),
+                RawCell::new("a"),
+            ],
+            vec![RawCell::new("b")],
+        ]
+    };
+
+    // Blank 模式只有左上角带类型
+    let data = build_table_data(rows(), SpanMode::Blank);
+    assert_eq!(data.cell_types.len(), 1);
+    assert_eq!(data.cell_type(0, 0, &[]), CellType::Number);
+
+    // Repeat 模式下重复的值与左上角类型相同
+    let data = build_table_data(rows(), SpanMode::Repeat);
+    for position in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+        assert_eq!(data.cell_types.get(&position), Some(&CellType::Number));
+    }
+    assert_eq!(data.cell_type(0, 2, &[]), CellType::Auto);
+}
+
+#[test]
+fn test_cell_types_follow_row_and_column_changes() {
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::new("隐藏"), RawCell::new("x")],
+            vec![
+                RawCell::new("2024-03-05").with_type(CellType::Date),
+                RawCell::new("007").with_type(CellType::Text),
+            ],
+        ],
+        SpanMode::Blank,
+    );
+
+    data.retain_rows(&[false, true]);
+    assert_eq!(data.cell_type(0, 0, &[]), CellType::Date);
+
+    data.select_columns(&[1, 0, 1]).unwrap();
+    assert_eq!(data.cell_type(0, 0, &[]), CellType::Text);
+    assert_eq!(data.cell_type(0, 1, &[]), CellType::Date);
+    assert_eq!(data.cell_type(0, 2, &[]), CellType::Text);
+}
+
+#[test]
+fn test_rowspan_anchor_type_moves_with_value() {
+    // 左上角所在行被移除时，类型与值一起移动到第一个保留的行
+    let mut data = build_table_data(
+        vec![
+            vec![
+                RawCell::with_span("42", 1, 2).with_type(CellType::Number),
+                RawCell::new("a"),
+            ],
+            vec![RawCell::new("b")],
+        ],
+        SpanMode::Blank,
+    );
+
+    data.retain_rows(&[false, true]);
+    assert_eq!(data.rows[0][0], "42");
+    assert_eq!(data.cell_type(0, 0, &[]), CellType::Number);
+}
+
+#[test]
+fn test_json_uses_cell_type() {
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::new("金额"), RawCell::new("编号")],
+            vec![
+                RawCell::new("1234.5").with_type(CellType::Number),
+                RawCell::new("1200").with_type(CellType::Text),
+            ],
+        ],
+        SpanMode::Blank,
+    );
+    data.header_rows = 1;
+
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Json);
+    options.set_json_infer_types(true);
+
+    let json = String::from_utf8(build_json_bytes(&data, &options).unwrap()).unwrap();
+    assert_eq!(json, r#"[{"金额":1234.5,"编号":"1200"}]"#);
+}
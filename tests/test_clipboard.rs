@@ -0,0 +1,48 @@
+//! 剪贴板内容测试
+//!
+//! 测试复制到剪贴板时 TSV 与 HTML 两种表示的生成，以及公式注入防护只作用于 TSV
+
+mod common;
+
+use belobog_stellar_grid::{
+    ExportFormat, ExportOptions, FormulaSanitization, TableData, build_clipboard_content,
+};
+
+fn tsv_options() -> ExportOptions {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Tsv);
+    options
+}
+
+fn table() -> TableData {
+    common::table(&[&["名称", "变化"], &["=1+1", "-5"]], 1)
+}
+
+#[test]
+fn test_clipboard_sanitizes_only_tsv() {
+    let (text, html) = build_clipboard_content(table(), &tsv_options()).unwrap();
+
+    assert!(text.contains("'=1+1\t-5"));
+    assert!(html.contains(">=1+1<"));
+    assert!(!html.contains("'=1+1"));
+}
+
+#[test]
+fn test_clipboard_uses_same_table_for_both() {
+    let mut options = tsv_options();
+    options.set_columns(Some(vec![1]));
+
+    let (text, html) = build_clipboard_content(table(), &options).unwrap();
+    assert!(text.starts_with("变化\r\n-5"));
+    assert!(html.contains(">变化<") && html.contains(">-5<"));
+    assert!(!text.contains("名称") && !html.contains("名称"));
+}
+
+#[test]
+fn test_clipboard_reject_still_fails() {
+    let mut options = tsv_options();
+    options.set_formula_sanitization(Some(FormulaSanitization::Reject));
+
+    let error = build_clipboard_content(table(), &options).unwrap_err();
+    assert_eq!(error.code(), "FORMULA_INJECTION");
+}
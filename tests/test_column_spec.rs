@@ -0,0 +1,190 @@
+//! 列配置测试
+//!
+//! 测试按列索引和表头文本选择、排除、重排列，以及导出时重命名表头
+
+use belobog_stellar_grid::{
+    ColumnRef, ColumnSpec, ExportError, RawCell, SpanGrid, SpanMode, build_table_data,
+    resolve_columns, select_row_columns,
+};
+
+fn texts(row: &[&str]) -> Vec<String> {
+    row.iter().map(|s| s.to_string()).collect()
+}
+
+fn header() -> Vec<Vec<String>> {
+    vec![texts(&["编号", " 姓名 ", "操作"])]
+}
+
+fn by_header(header: &str) -> ColumnSpec {
+    ColumnSpec {
+        column: ColumnRef::Header(header.to_string()),
+        title: None,
+    }
+}
+
+#[test]
+fn test_reorder_and_rename_by_header() {
+    let spec = vec![
+        ColumnSpec {
+            column: ColumnRef::Header("姓名".to_string()),
+            title: Some("Name".to_string()),
+        },
+        ColumnSpec {
+            column: ColumnRef::Index(0),
+            title: None,
+        },
+    ];
+
+    let selection = resolve_columns(&header(), 3, &spec, &[]).unwrap();
+    assert_eq!(selection.columns, vec![1, 0]);
+    assert_eq!(selection.titles, vec![Some("Name".to_string()), None]);
+}
+
+#[test]
+fn test_exclude_without_spec_keeps_order() {
+    let exclude = vec![ColumnRef::Header("操作".to_string())];
+
+    let selection = resolve_columns(&header(), 3, &[], &exclude).unwrap();
+    assert_eq!(selection.columns, vec![0, 1]);
+    assert_eq!(selection.titles, vec![None, None]);
+}
+
+#[test]
+fn test_exclude_applies_after_spec() {
+    let spec = vec![by_header("操作"), by_header("编号")];
+    let exclude = vec![ColumnRef::Index(2)];
+
+    let selection = resolve_columns(&header(), 3, &spec, &exclude).unwrap();
+    assert_eq!(selection.columns, vec![0]);
+}
+
+#[test]
+fn test_header_matches_any_header_row() {
+    // 多行表头中，上层合并单元格只出现在其第一列
+    let header_rows = vec![
+        texts(&["基本信息", "", "成绩"]),
+        texts(&["编号", "姓名", "总分"]),
+    ];
+
+    let selection = resolve_columns(&header_rows, 3, &[by_header("成绩")], &[]).unwrap();
+    assert_eq!(selection.columns, vec![2]);
+    let selection = resolve_columns(&header_rows, 3, &[by_header("姓名")], &[]).unwrap();
+    assert_eq!(selection.columns, vec![1]);
+}
+
+#[test]
+fn test_unknown_header_and_index() {
+    let error = resolve_columns(&header(), 3, &[by_header("备注")], &[]).unwrap_err();
+    assert_eq!(
+        error,
+        ExportError::ColumnNotFound {
+            header: "备注".to_string()
+        }
+    );
+    assert_eq!(error.code(), "COLUMN_NOT_FOUND");
+
+    let error = resolve_columns(&header(), 3, &[], &[ColumnRef::Index(5)]).unwrap_err();
+    assert_eq!(
+        error,
+        ExportError::ColumnOutOfRange {
+            column: 5,
+            width: 3
+        }
+    );
+
+    // 没有表头行时无法按表头文本查找
+    assert!(resolve_columns(&[], 3, &[by_header("编号")], &[]).is_err());
+}
+
+#[test]
+fn test_rename_headers_uses_last_header_row() {
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::with_span("人员", 2, 1)],
+            vec![RawCell::new("编号"), RawCell::new("姓名")],
+            vec![RawCell::new("1"), RawCell::new("张三")],
+        ],
+        SpanMode::Blank,
+    );
+    data.header_rows = 2;
+
+    let selection = resolve_columns(
+        &data.rows[..2],
+        2,
+        &[
+            ColumnSpec {
+                column: ColumnRef::Header("姓名".to_string()),
+                title: Some("Name".to_string()),
+            },
+            by_header("编号"),
+        ],
+        &[],
+    )
+    .unwrap();
+    data.select_columns(&selection.columns).unwrap();
+    data.rename_headers(&selection.titles);
+
+    // 合并区域内的列被重排时，值保留在新的左上角
+
+    assert_eq!(
+        data.rows,
+        vec![
+            texts(&["人员", ""]),
+            texts(&["Name", "编号"]),
+            texts(&["张三", "1"])
+        ]
+    );
+
+    // 没有表头行时不重命名
+    data.header_rows = 0;
+    data.rename_headers(&[Some("x".to_string())]);
+    assert_eq!(data.rows[0], texts(&["人员", ""]));
+}
+
+#[test]
+fn test_streaming_selection_matches_table_selection() {
+    // | 编号(rowspan=2) | 姓名(colspan=2) | 备注 |
+    // |                 | 姓   | 名       |      |
+    // 分批导出逐行选择列，结果应与一次性导出的 TableData::select_columns 相同
+    let raw_rows = vec![
+        vec![
+            RawCell::with_span("编号", 1, 2),
+            RawCell::with_span("姓名", 2, 1),
+            RawCell::new("备注"),
+        ],
+        vec![RawCell::new("姓"), RawCell::new("名"), RawCell::new("")],
+        vec![
+            RawCell::new("1"),
+            RawCell::new("张"),
+            RawCell::new("三"),
+            RawCell::with_span("无", 2, 1),
+        ],
+    ];
+
+    for mode in [SpanMode::Blank, SpanMode::Repeat] {
+        for columns in [vec![1], vec![2, 0], vec![3, 2, 1], vec![2, 1, 2], vec![4]] {
+            let mut expected = build_table_data(raw_rows.clone(), mode);
+            expected.select_columns(&columns).unwrap();
+
+            let mut grid = SpanGrid::new(mode);
+            let streamed: Vec<Vec<String>> = raw_rows
+                .iter()
+                .map(|raw_row| {
+                    let mut row = grid.push_row(raw_row.clone());
+                    row.resize(5, String::new());
+                    select_row_columns(&row, &columns, grid.last_row_merges())
+                })
+                .collect();
+
+            assert_eq!(streamed, expected.rows, "{mode:?} {columns:?}");
+        }
+    }
+
+    // 只选中两级表头合并区域的第二列时，值移到该列而不是留空
+    let mut grid = SpanGrid::new(SpanMode::Blank);
+    let header = grid.push_row(raw_rows[0].clone());
+    assert_eq!(
+        select_row_columns(&header, &[2], grid.last_row_merges()),
+        texts(&["姓名"])
+    );
+}
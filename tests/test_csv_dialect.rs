@@ -0,0 +1,106 @@
+//! CSV 方言测试
+//!
+//! 测试分隔符、引号字符、引号策略、换行符和转义方式对 CSV 输出的影响
+
+mod common;
+
+use belobog_stellar_grid::{
+    CsvQuoteStyle, ExportFormat, ExportOptions, LineTerminator, TableData, build_csv_bytes,
+};
+use common::table;
+
+fn render(table_data: &TableData, options: &ExportOptions) -> String {
+    String::from_utf8(build_csv_bytes(table_data, options).unwrap()).unwrap()
+}
+
+#[test]
+fn test_default_dialect() {
+    let data = table(&[&["名称", "数量"], &["a,b", "3"]], 0);
+    let options = ExportOptions::new();
+
+    assert_eq!(render(&data, &options), "名称,数量\r\n\"a,b\",3\r\n");
+}
+
+#[test]
+fn test_semicolon_delimiter_and_lf() {
+    let data = table(&[&["名称", "金额"], &["a,b", "1,5"]], 0);
+    let mut options = ExportOptions::new();
+    options.set_delimiter(";").unwrap();
+    options.set_line_terminator(LineTerminator::Lf);
+
+    assert_eq!(render(&data, &options), "名称;金额\na,b;1,5\n");
+}
+
+#[test]
+fn test_quote_styles() {
+    let data = table(&[&["x", "1"]], 0);
+    let mut options = ExportOptions::new();
+
+    options.set_quote_style(CsvQuoteStyle::Always);
+    assert_eq!(render(&data, &options), "\"x\",\"1\"\r\n");
+
+    options.set_quote_style(CsvQuoteStyle::NonNumeric);
+    assert_eq!(render(&data, &options), "\"x\",1\r\n");
+
+    let data = table(&[&["a,b"]], 0);
+    options.set_quote_style(CsvQuoteStyle::Never);
+    assert_eq!(render(&data, &options), "a,b\r\n");
+}
+
+#[test]
+fn test_custom_quote_and_escape() {
+    let data = table(&[&["say \"hi\"", "it's"]], 0);
+    let mut options = ExportOptions::new();
+
+    assert_eq!(render(&data, &options), "\"say \"\"hi\"\"\",it's\r\n");
+
+    options.set_double_quote(false);
+    assert_eq!(render(&data, &options), "\"say \\\"hi\\\"\",it's\r\n");
+
+    options.set_escape("!").unwrap();
+    assert_eq!(render(&data, &options), "\"say !\"hi!\"\",it's\r\n");
+
+    let mut options = ExportOptions::new();
+    options.set_quote("'").unwrap();
+    assert_eq!(render(&data, &options), "say \"hi\",'it''s'\r\n");
+}
+
+#[test]
+fn test_dialect_setters_reject_invalid_characters() {
+    let mut options = ExportOptions::new();
+
+    assert_eq!(
+        options.set_quote("").unwrap_err().code(),
+        "INVALID_ARGUMENT"
+    );
+    assert_eq!(
+        options.set_escape("\\\\").unwrap_err().code(),
+        "INVALID_ARGUMENT"
+    );
+    assert_eq!(options.quote(), "\"");
+    assert_eq!(options.escape(), "\\");
+    assert!(options.double_quote());
+    assert_eq!(options.quote_style(), CsvQuoteStyle::Necessary);
+    assert_eq!(options.line_terminator(), LineTerminator::Crlf);
+}
+
+#[test]
+fn test_tsv_uses_tab_and_quotes_embedded_tabs_and_newlines() {
+    let data = table(
+        &[
+            &["名称", "备注"],
+            &["a,b", "x\ty"],
+            &["多行", "第一行\n第二行"],
+        ],
+        0,
+    );
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Tsv);
+    // TSV 忽略自定义分隔符
+    options.set_delimiter(";").unwrap();
+
+    assert_eq!(
+        render(&data, &options),
+        "名称\t备注\r\na,b\t\"x\ty\"\r\n多行\t\"第一行\n第二行\"\r\n"
+    );
+}
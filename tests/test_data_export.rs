@@ -0,0 +1,37 @@
+//! JavaScript 数据导出测试
+//!
+//! 测试 export_data 中 JavaScript 值到单元格文本的转换
+
+use belobog_stellar_grid::{CellValue, format_number, infer_cell_value};
+
+#[test]
+fn test_format_number_integers() {
+    assert_eq!(format_number(42.0), "42");
+    assert_eq!(format_number(-7.0), "-7");
+    assert_eq!(format_number(0.0), "0");
+    assert_eq!(format_number(-0.0), "0");
+}
+
+#[test]
+fn test_format_number_decimals() {
+    assert_eq!(format_number(3.25), "3.25");
+    assert_eq!(format_number(0.1), "0.1");
+}
+
+#[test]
+fn test_format_number_non_finite() {
+    assert_eq!(format_number(f64::NAN), "");
+    assert_eq!(format_number(f64::INFINITY), "");
+    assert_eq!(format_number(f64::NEG_INFINITY), "");
+}
+
+#[test]
+fn test_format_number_round_trips_through_inference() {
+    // JavaScript 数字转换为文本后，Excel 导出应能重新识别为数字
+    for number in [1.0, -12.5, 1234567.0, 0.001] {
+        match infer_cell_value(&format_number(number)) {
+            CellValue::Number { value, .. } => assert_eq!(value, number),
+            other => panic!("{} 未被识别为数字: {:?}", number, other),
+        }
+    }
+}
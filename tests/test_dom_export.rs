@@ -0,0 +1,351 @@
+//! 浏览器 DOM 导出测试
+//!
+//! 在真实浏览器中构造表格，测试从 DOM 读取单元格以及一次性导出与分批导出结果的一致性。
+//! 需要通过 `wasm-pack test --headless --chrome` 运行
+
+#![cfg(target_arch = "wasm32")]
+
+use belobog_stellar_grid::{
+    ExportFormat, ExportOptions, LineTerminator, LinkFormat, export_table_batch_to_bytes,
+    export_table_batch_with_options, export_table_to_bytes_with_options, export_table_to_string,
+};
+use std::io::{Cursor, Read};
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{AbortController, Element, HtmlInputElement};
+use zip::ZipArchive;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// 把 HTML 片段挂载到页面上，返回容器以便测试结束后移除
+fn mount(html: &str) -> Element {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let container = document.create_element("div").unwrap();
+    container.set_inner_html(html);
+    document.body().unwrap().append_child(&container).unwrap();
+    container
+}
+
+fn csv_options() -> ExportOptions {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Csv);
+    options.set_line_terminator(LineTerminator::Lf);
+    options.set_header_rows(Some(1));
+    options.set_batch_size(1).unwrap();
+    options
+}
+
+async fn batch_csv(table_id: &str, options: &ExportOptions) -> String {
+    let bytes = export_table_batch_to_bytes(table_id, options)
+        .await
+        .unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_batch_csv_pads_ragged_rows_like_sync() {
+    let container = mount(
+        r#"<table id="ragged-rows">
+            <tr><th>a</th><th>b</th><th>c</th></tr>
+            <tr><td>1</td></tr>
+            <tr><td>2</td><td>3</td></tr>
+        </table>"#,
+    );
+    let options = csv_options();
+
+    let sync = export_table_to_string("ragged-rows", &options).unwrap();
+    let batch = batch_csv("ragged-rows", &options).await;
+    container.remove();
+
+    assert_eq!(sync, "a,b,c\n1,,\n2,3,\n");
+    assert_eq!(batch, sync);
+}
+
+#[wasm_bindgen_test]
+async fn test_batch_csv_accepts_row_wider_than_header() {
+    // 比表头更宽的行无法回头补齐之前的行，但不应导致导出失败
+    let container = mount(
+        r#"<table id="wide-row">
+            <tr><th>a</th></tr>
+            <tr><td>1</td><td>2</td></tr>
+            <tr><td>3</td></tr>
+        </table>"#,
+    );
+
+    let batch = batch_csv("wide-row", &csv_options()).await;
+    container.remove();
+
+    assert_eq!(batch, "a\n1,2\n3,\n");
+}
+
+#[wasm_bindgen_test]
+async fn test_batch_csv_column_selection_matches_sync() {
+    // 只选中合并表头的第二列时，两种导出方式都应输出表头文本而不是空值
+    let container = mount(
+        r#"<table id="merged-header">
+            <thead>
+                <tr><th colspan="2">姓名</th><th rowspan="2">年龄</th></tr>
+                <tr><th>姓</th><th>名</th></tr>
+            </thead>
+            <tbody>
+                <tr><td>张</td><td>三</td><td>20</td></tr>
+            </tbody>
+        </table>"#,
+    );
+    let mut options = csv_options();
+    options.set_header_rows(Some(2));
+    options.set_columns(Some(vec![1, 2]));
+
+    let sync = export_table_to_string("merged-header", &options).unwrap();
+    let batch = batch_csv("merged-header", &options).await;
+    container.remove();
+
+    assert_eq!(sync, "姓名,年龄\n名,\n三,20\n");
+    assert_eq!(batch, sync);
+}
+
+#[wasm_bindgen_test]
+async fn test_batch_exclude_columns_keeps_wider_columns() {
+    // 只排除列时，比表头更宽的行中新出现的列应与一次性导出一样保留
+    let container = mount(
+        r#"<table id="exclude-ragged">
+            <tr><th>a</th><th>b</th><th>c</th></tr>
+            <tr><td>1</td><td>2</td><td>3</td><td>4</td></tr>
+            <tr><td>5</td></tr>
+        </table>"#,
+    );
+    let mut options = csv_options();
+    options
+        .set_exclude_columns(js_sys::Array::of1(&"b".into()).into())
+        .unwrap();
+
+    let sync = export_table_to_string("exclude-ragged", &options).unwrap();
+    let batch = batch_csv("exclude-ragged", &options).await;
+    container.remove();
+
+    assert_eq!(sync, "a,c,\n1,3,4\n5,,\n");
+    // 表头行写出时还不知道后面更宽的行，只有数据行与一次性导出相同
+    assert_eq!(batch, "a,c\n1,3,4\n5,,\n");
+}
+
+#[wasm_bindgen_test]
+async fn test_batch_export_aborts_between_batches() {
+    let container = mount(
+        r#"<table id="abort-table">
+            <tr><th>a</th></tr>
+            <tr><td>1</td></tr>
+            <tr><td>2</td></tr>
+            <tr><td>3</td></tr>
+        </table>"#,
+    );
+
+    // 第一批完成后中止，导出应在下一批之前停止
+    let controller = AbortController::new().unwrap();
+    let abort = Closure::<dyn Fn(f64)>::new({
+        let controller = controller.clone();
+        move |progress| {
+            if progress > 0.0 {
+                controller.abort();
+            }
+        }
+    });
+    let mut options = csv_options();
+    options.set_signal(Some(controller.signal()));
+    options.set_progress_callback(Some(
+        abort.as_ref().unchecked_ref::<js_sys::Function>().clone(),
+    ));
+
+    let error = JsFuture::from(export_table_batch_with_options(
+        "abort-table".to_string(),
+        &options,
+    ))
+    .await
+    .unwrap_err();
+    container.remove();
+
+    let error: js_sys::Error = error.dyn_into().unwrap();
+    assert_eq!(error.name(), "AbortError");
+    assert_eq!(
+        js_sys::Reflect::get(&error, &"code".into()).unwrap(),
+        "CANCELLED"
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_skip_hidden_rows_and_cells() {
+    let container = mount(
+        r#"<table id="hidden-parts">
+            <thead><tr><th>a</th><th style="display: none">b</th><th>c</th></tr></thead>
+            <tbody>
+                <tr><td>1</td><td style="display: none">2</td><td>3</td></tr>
+                <tr hidden><td>4</td><td>5</td><td>6</td></tr>
+                <tr style="visibility: collapse"><td>7</td><td>8</td><td>9</td></tr>
+                <tr><td aria-hidden="true">x</td><td>10</td><td>11</td></tr>
+            </tbody>
+            <tbody style="display: none"><tr><td>12</td><td>13</td><td>14</td></tr></tbody>
+        </table>"#,
+    );
+    let mut options = csv_options();
+
+    let all = export_table_to_string("hidden-parts", &options).unwrap();
+    options.set_skip_hidden(true);
+    let visible = export_table_to_string("hidden-parts", &options).unwrap();
+    let batch = batch_csv("hidden-parts", &options).await;
+    container.remove();
+
+    assert_eq!(all.lines().count(), 6);
+    assert_eq!(visible, "a,c\n1,3\n10,11\n");
+    assert_eq!(batch, visible);
+}
+
+fn json_options() -> ExportOptions {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Json);
+    options.set_json_infer_types(true);
+    options.set_header_rows(Some(1));
+    options
+}
+
+#[wasm_bindgen_test]
+fn test_export_value_and_type_attributes() {
+    let container = mount(
+        r#"<table id="export-attributes">
+            <tr><th>金额</th><th>编号</th><th>日期</th></tr>
+            <tr>
+                <td data-export-value="1234.5" data-export-type="number">¥1,234.50</td>
+                <td data-export-type="text">1200</td>
+                <td data-export-value="2024-03-05">3月5日</td>
+            </tr>
+        </table>"#,
+    );
+
+    let csv = export_table_to_string("export-attributes", &csv_options()).unwrap();
+    let json = export_table_to_string("export-attributes", &json_options()).unwrap();
+    container.remove();
+
+    assert_eq!(csv, "金额,编号,日期\n1234.5,1200,2024-03-05\n");
+    assert_eq!(
+        json,
+        r#"[{"金额":1234.5,"编号":"1200","日期":"2024-03-05"}]"#
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_form_control_values() {
+    let container = mount(
+        r#"<table id="form-controls">
+            <tr><th>数量</th><th>城市</th><th>启用</th><th>备注</th><th>操作</th></tr>
+            <tr>
+                <td><input id="form-quantity" value="1"></td>
+                <td><select><option>北京</option><option selected>上海</option></select></td>
+                <td><input id="form-enabled" type="checkbox"></td>
+                <td><textarea>说明</textarea><input type="hidden" value="secret"></td>
+                <td><button>删除</button></td>
+            </tr>
+        </table>"#,
+    );
+
+    // 导出的是控件的当前值，而不是 HTML 中的初始值
+    let document = web_sys::window().unwrap().document().unwrap();
+    document
+        .get_element_by_id("form-quantity")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .set_value("7");
+    document
+        .get_element_by_id("form-enabled")
+        .unwrap()
+        .dyn_into::<HtmlInputElement>()
+        .unwrap()
+        .set_checked(true);
+
+    let csv = export_table_to_string("form-controls", &csv_options()).unwrap();
+    let json = export_table_to_string("form-controls", &json_options()).unwrap();
+    container.remove();
+
+    assert_eq!(csv, "数量,城市,启用,备注,操作\n7,上海,TRUE,说明,删除\n");
+    assert_eq!(
+        json,
+        r#"[{"数量":7,"城市":"上海","启用":true,"备注":"说明","操作":"删除"}]"#
+    );
+}
+
+fn links_table() -> Element {
+    mount(
+        r#"<table id="links">
+            <tr><th>记录</th><th>脚本</th></tr>
+            <tr>
+                <td><a href="https://example.com/records/42">#42</a></td>
+                <td><a href="javascript:void(0)">查看</a></td>
+            </tr>
+            <tr><td><a href="/records/7">#7</a></td><td>无</td></tr>
+        </table>"#,
+    )
+}
+
+#[wasm_bindgen_test]
+fn test_csv_link_formats() {
+    let container = links_table();
+    let mut options = csv_options();
+
+    let text = export_table_to_string("links", &options).unwrap();
+    options.set_link_format(LinkFormat::Both);
+    let both = export_table_to_string("links", &options).unwrap();
+    container.remove();
+
+    assert_eq!(text, "记录,脚本\n#42,查看\n#7,无\n");
+
+    // 相对地址按页面地址解析为绝对地址，不可导出的协议只保留文本
+    let origin = web_sys::window().unwrap().location().origin().unwrap();
+    assert_eq!(
+        both,
+        format!(
+            "记录,脚本\n#42 (https://example.com/records/42),查看\n#7 ({origin}/records/7),无\n"
+        )
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_xlsx_cell_hyperlinks() {
+    let container = links_table();
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Xlsx);
+
+    let bytes = export_table_to_bytes_with_options("links", &options)
+        .unwrap()
+        .to_vec();
+    container.remove();
+
+    let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut sheet = String::new();
+    archive
+        .by_name("xl/worksheets/sheet1.xml")
+        .unwrap()
+        .read_to_string(&mut sheet)
+        .unwrap();
+    assert!(sheet.contains(r#"<hyperlink ref="A2""#));
+    assert!(sheet.contains(r#"<hyperlink ref="A3""#));
+    assert!(!sheet.contains(r#"<hyperlink ref="B2""#));
+}
+
+#[wasm_bindgen_test]
+fn test_markdown_alignment_follows_expanded_columns() {
+    // 表头的 rowspan 把数据行的单元格推到右侧，对齐方式应跟随展开后的列
+    let container = mount(
+        r#"<table id="aligned">
+            <tr><th rowspan="2">类别</th><th>名称</th><th>备注</th></tr>
+            <tr><td style="text-align: center">苹果</td><td style="text-align: left">红色</td></tr>
+            <tr><td>水果</td><td>梨</td><td>黄色</td></tr>
+        </table>"#,
+    );
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Markdown);
+
+    let markdown = export_table_to_string("aligned", &options).unwrap();
+    container.remove();
+
+    assert_eq!(markdown.lines().nth(1), Some("| --- | :---: | :--- |"));
+}
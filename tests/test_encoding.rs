@@ -0,0 +1,85 @@
+//! CSV 编码测试
+//!
+//! 测试 UTF-8 BOM、UTF-16LE 以及 GBK、Shift_JIS 等传统编码的转换
+
+use belobog_stellar_grid::{
+    CsvEncoding, ExportError, ExportOptions, RawCell, SpanMode, build_csv_bytes, build_table_data,
+    encode_text,
+};
+
+#[test]
+fn test_utf8_without_and_with_bom() {
+    let text = "名称\r\n".as_bytes().to_vec();
+
+    assert_eq!(
+        encode_text(text.clone(), CsvEncoding::Utf8, false).unwrap(),
+        text
+    );
+
+    let with_bom = encode_text(text.clone(), CsvEncoding::Utf8, true).unwrap();
+    assert_eq!(&with_bom[..3], &[0xEF, 0xBB, 0xBF]);
+    assert_eq!(&with_bom[3..], text.as_slice());
+}
+
+#[test]
+fn test_utf16le_always_has_bom() {
+    let bytes = encode_text("a中".as_bytes().to_vec(), CsvEncoding::Utf16Le, false).unwrap();
+
+    assert_eq!(bytes, vec![0xFF, 0xFE, 0x61, 0x00, 0x2D, 0x4E]);
+}
+
+#[test]
+fn test_legacy_encodings() {
+    let gbk = encode_text("中文,1".as_bytes().to_vec(), CsvEncoding::Gbk, true).unwrap();
+    assert_eq!(gbk, vec![0xD6, 0xD0, 0xCE, 0xC4, b',', b'1']);
+
+    let sjis = encode_text("日本".as_bytes().to_vec(), CsvEncoding::ShiftJis, false).unwrap();
+    assert_eq!(sjis, vec![0x93, 0xFA, 0x96, 0x7B]);
+
+    let big5 = encode_text("中文".as_bytes().to_vec(), CsvEncoding::Big5, false).unwrap();
+    assert_eq!(big5, vec![0xA4, 0xA4, 0xA4, 0xE5]);
+
+    // GB18030 可以表示所有 Unicode 字符
+    assert!(encode_text("😀".as_bytes().to_vec(), CsvEncoding::Gb18030, false).is_ok());
+}
+
+#[test]
+fn test_unencodable_character_is_reported() {
+    let err = encode_text(
+        "日本 €5 😀".as_bytes().to_vec(),
+        CsvEncoding::ShiftJis,
+        false,
+    )
+    .unwrap_err();
+
+    assert_eq!(err.code(), "ENCODING_FAILED");
+    assert_eq!(
+        err,
+        ExportError::EncodingFailed {
+            encoding: "shift_jis",
+            character: '€',
+        }
+    );
+}
+
+#[test]
+fn test_csv_export_applies_encoding_options() {
+    let data = build_table_data(
+        vec![vec![RawCell::new("名称")], vec![RawCell::new("值")]],
+        SpanMode::Blank,
+    );
+    let mut options = ExportOptions::new();
+    assert_eq!(options.encoding(), CsvEncoding::Utf8);
+    assert!(!options.bom());
+
+    options.set_bom(true);
+    let bytes = build_csv_bytes(&data, &options).unwrap();
+    assert_eq!(
+        bytes,
+        [&[0xEF, 0xBB, 0xBF][..], "名称\r\n值\r\n".as_bytes()].concat()
+    );
+
+    options.set_encoding(CsvEncoding::Gb18030);
+    let bytes = build_csv_bytes(&data, &options).unwrap();
+    assert_eq!(&bytes[..4], &[0xC3, 0xFB, 0xB3, 0xC6]);
+}
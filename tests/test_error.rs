@@ -0,0 +1,107 @@
+//! 错误类型测试
+//!
+//! 测试 ExportError 的错误码与错误信息，以及各验证函数返回的错误类型
+
+#[allow(deprecated)]
+use belobog_stellar_grid::validate_filename_legacy;
+use belobog_stellar_grid::{ExportError, ExportOptions, Message, validate_filename};
+
+#[test]
+fn test_error_codes_are_stable() {
+    let cases = [
+        (
+            ExportError::TableNotFound {
+                table_id: "t".to_string(),
+            },
+            "TABLE_NOT_FOUND",
+        ),
+        (ExportError::EmptyTable, "EMPTY_TABLE"),
+        (
+            ExportError::InvalidFilename {
+                filename: "a/b".to_string(),
+                reason: Message::FilenamePathSeparator,
+            },
+            "INVALID_FILENAME",
+        ),
+        (
+            ExportError::ReadFailed {
+                row: 0,
+                column: None,
+            },
+            "READ_FAILED",
+        ),
+        (
+            ExportError::ColumnOutOfRange {
+                column: 3,
+                width: 2,
+            },
+            "COLUMN_OUT_OF_RANGE",
+        ),
+        (
+            ExportError::ColumnNotFound {
+                header: "操作".to_string(),
+            },
+            "COLUMN_NOT_FOUND",
+        ),
+        (ExportError::Cancelled, "CANCELLED"),
+    ];
+
+    for (error, code) in cases {
+        assert_eq!(error.code(), code);
+    }
+}
+
+#[test]
+fn test_error_messages_use_one_based_positions() {
+    let error = ExportError::ReadFailed {
+        row: 0,
+        column: Some(2),
+    };
+    assert_eq!(error.to_string(), "无法读取第 1 行第 3 列单元格");
+
+    let error = ExportError::TableNotFound {
+        table_id: "sales".to_string(),
+    };
+    assert_eq!(error.to_string(), "找不到 ID 为 'sales' 的表格元素");
+}
+
+#[test]
+fn test_validate_filename_returns_invalid_filename() {
+    let error = validate_filename("../etc/passwd").unwrap_err();
+
+    assert_eq!(error.code(), "INVALID_FILENAME");
+    match error {
+        ExportError::InvalidFilename { filename, reason } => {
+            assert_eq!(filename, "../etc/passwd");
+            assert_eq!(reason, Message::FilenamePathSeparator);
+        }
+        other => panic!("意外的错误类型: {:?}", other),
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_validate_filename_legacy_keeps_string_errors() {
+    // 旧签名返回与之前版本相同的中文错误信息
+    assert_eq!(validate_filename_legacy("report.csv"), Ok(()));
+    assert_eq!(
+        validate_filename_legacy("../etc/passwd"),
+        Err("文件名不能包含路径分隔符".to_string())
+    );
+    assert_eq!(
+        validate_filename_legacy("CON.csv"),
+        Err("文件名 'CON' 是系统保留名称".to_string())
+    );
+}
+
+#[test]
+fn test_option_setters_return_invalid_argument() {
+    let mut options = ExportOptions::new();
+
+    let error = options.set_batch_size(0).unwrap_err();
+    assert_eq!(error.code(), "INVALID_ARGUMENT");
+
+    let error = options.set_delimiter("；").unwrap_err();
+    assert_eq!(error.code(), "INVALID_ARGUMENT");
+    assert_eq!(options.delimiter(), ",");
+}
@@ -0,0 +1,210 @@
+//! 导出配置与表格数据变换测试
+//!
+//! 测试 ExportOptions 的默认值和属性设置，以及表头移除、隐藏行过滤、列选择对表格数据的影响
+
+use belobog_stellar_grid::{
+    CellType, ExportError, ExportFormat, ExportOptions, LinkFormat, MergeRange, RawCell, SpanMode,
+    build_table_data,
+};
+
+fn texts(row: &[&str]) -> Vec<String> {
+    row.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_export_options_defaults() {
+    let options = ExportOptions::new();
+
+    assert_eq!(options.format(), ExportFormat::Csv);
+    assert_eq!(options.filename(), None);
+    assert_eq!(options.sheet_name(), None);
+    assert_eq!(options.delimiter(), ",");
+    assert_eq!(options.header_rows(), None);
+    assert!(options.include_header());
+    assert_eq!(options.columns(), None);
+    assert!(options.column_types().is_empty());
+    assert_eq!(options.span_mode(), SpanMode::Blank);
+    assert!(!options.skip_hidden());
+    assert_eq!(options.link_format(), LinkFormat::Text);
+    assert_eq!(options.tbody_id(), None);
+    assert_eq!(options.batch_size(), 1000);
+    assert!(options.signal().is_none());
+}
+
+#[test]
+fn test_export_options_setters() {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Xlsx);
+    options.set_filename(Some("报表".to_string()));
+    options.set_sheet_name(Some("汇总".to_string()));
+    options.set_delimiter(";").unwrap();
+    options.set_header_rows(Some(2));
+    options.set_include_header(false);
+    options.set_columns(Some(vec![2, 0]));
+    options.set_column_types(Some(vec![CellType::Text]));
+    options.set_batch_size(50).unwrap();
+    options.set_skip_hidden(true);
+    options.set_link_format(LinkFormat::Both);
+
+    assert_eq!(options.format(), ExportFormat::Xlsx);
+    assert_eq!(options.filename().as_deref(), Some("报表"));
+    assert_eq!(options.sheet_name().as_deref(), Some("汇总"));
+    assert_eq!(options.delimiter(), ";");
+    assert_eq!(options.header_rows(), Some(2));
+    assert!(!options.include_header());
+    assert_eq!(options.columns(), Some(vec![2, 0]));
+    assert_eq!(options.column_types(), vec![CellType::Text]);
+    assert_eq!(options.batch_size(), 50);
+    assert!(options.skip_hidden());
+    assert_eq!(options.link_format(), LinkFormat::Both);
+
+    options.set_column_types(None);
+    assert!(options.column_types().is_empty());
+}
+
+#[test]
+fn test_remove_header_rows() {
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::new("姓名"), RawCell::new("分数")],
+            vec![RawCell::new("张三"), RawCell::new("90")],
+        ],
+        SpanMode::Blank,
+    );
+    data.header_rows = 1;
+
+    data.remove_header_rows();
+
+    assert_eq!(data.rows, vec![texts(&["张三", "90"])]);
+    assert_eq!(data.header_rows, 0);
+}
+
+#[test]
+fn test_remove_header_rows_truncates_spanning_merge() {
+    // | 类别(rowspan=3) | 表头 |
+    // |                 | A    |
+    // |                 | B    |
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::with_span("类别", 1, 3), RawCell::new("表头")],
+            vec![RawCell::new("A")],
+            vec![RawCell::new("B")],
+        ],
+        SpanMode::Blank,
+    );
+    data.header_rows = 1;
+
+    data.remove_header_rows();
+
+    assert_eq!(data.rows, vec![texts(&["类别", "A"]), texts(&["", "B"])]);
+    assert_eq!(
+        data.merges,
+        vec![MergeRange {
+            first_row: 0,
+            first_col: 0,
+            last_row: 1,
+            last_col: 0,
+        }]
+    );
+}
+
+#[test]
+fn test_retain_rows_adjusts_header_rows() {
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::new("筛选")],
+            vec![RawCell::new("姓名")],
+            vec![RawCell::new("张三")],
+            vec![RawCell::new("李四")],
+        ],
+        SpanMode::Blank,
+    );
+    data.header_rows = 2;
+
+    // 长度不足的部分视为保留
+    data.retain_rows(&[false, true, false]);
+
+    assert_eq!(data.rows, vec![texts(&["姓名"]), texts(&["李四"])]);
+    assert_eq!(data.header_rows, 1);
+}
+
+#[test]
+fn test_retain_rows_shrinks_merges() {
+    // | 华东(rowspan=3) | A |  <- 隐藏
+    // |                 | B |
+    // |                 | C |
+    // | 华北            | D |  <- 隐藏
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::with_span("华东", 1, 3), RawCell::new("A")],
+            vec![RawCell::new("B")],
+            vec![RawCell::new("C")],
+            vec![RawCell::new("华北"), RawCell::new("D")],
+        ],
+        SpanMode::Blank,
+    );
+
+    data.retain_rows(&[false, true, true, false]);
+
+    assert_eq!(data.rows, vec![texts(&["华东", "B"]), texts(&["", "C"])]);
+    assert_eq!(
+        data.merges,
+        vec![MergeRange {
+            first_row: 0,
+            first_col: 0,
+            last_row: 1,
+            last_col: 0,
+        }]
+    );
+
+    // 只剩一行的合并区域不再保留
+    data.retain_rows(&[true, false]);
+    assert_eq!(data.rows, vec![texts(&["华东", "B"])]);
+    assert!(data.merges.is_empty());
+}
+
+#[test]
+fn test_select_columns_reorders() {
+    let mut data = build_table_data(
+        vec![vec![
+            RawCell::new("A"),
+            RawCell::new("B"),
+            RawCell::new("C"),
+        ]],
+        SpanMode::Blank,
+    );
+
+    data.select_columns(&[2, 0]).unwrap();
+
+    assert_eq!(data.rows, vec![texts(&["C", "A"])]);
+}
+
+#[test]
+fn test_select_columns_remaps_merges() {
+    // | 成绩(colspan=2) | 备注 |
+    let mut data = build_table_data(
+        vec![vec![RawCell::with_span("成绩", 2, 1), RawCell::new("备注")]],
+        SpanMode::Blank,
+    );
+
+    data.select_columns(&[1, 2]).unwrap();
+
+    // 合并区域只剩一列，左上角的值移到新的位置
+    assert_eq!(data.rows, vec![texts(&["成绩", "备注"])]);
+    assert!(data.merges.is_empty());
+}
+
+#[test]
+fn test_select_columns_out_of_range() {
+    let mut data = build_table_data(vec![vec![RawCell::new("A")]], SpanMode::Blank);
+
+    let err = data.select_columns(&[1]).unwrap_err();
+    assert_eq!(
+        err,
+        ExportError::ColumnOutOfRange {
+            column: 1,
+            width: 1
+        }
+    );
+    assert!(err.to_string().contains("超出范围"));
+}
@@ -0,0 +1,46 @@
+//! 表单控件值测试
+//!
+//! 测试单元格中输入框、下拉框、复选框等控件的值如何合并为导出的单元格值
+
+use belobog_stellar_grid::{CellType, FormValue, form_cell_value};
+
+fn text(value: &str) -> FormValue {
+    FormValue::Text(value.to_string())
+}
+
+#[test]
+fn test_single_checkbox_is_boolean() {
+    assert_eq!(
+        form_cell_value(&[FormValue::Checked(true)]),
+        ("TRUE".to_string(), Some(CellType::Boolean))
+    );
+    assert_eq!(
+        form_cell_value(&[FormValue::Checked(false)]),
+        ("FALSE".to_string(), Some(CellType::Boolean))
+    );
+}
+
+#[test]
+fn test_single_value_is_kept() {
+    assert_eq!(
+        form_cell_value(&[text("00123")]),
+        ("00123".to_string(), None)
+    );
+    // 未选择任何选项的下拉框或空输入框导出为空单元格
+    assert_eq!(form_cell_value(&[text("")]), (String::new(), None));
+    // 只有未选中的单选框
+    assert_eq!(form_cell_value(&[]), (String::new(), None));
+}
+
+#[test]
+fn test_multiple_controls_are_joined() {
+    assert_eq!(
+        form_cell_value(&[text("北京"), text(""), text("朝阳区")]),
+        ("北京, 朝阳区".to_string(), None)
+    );
+    // 与其他控件一起出现的复选框不再指定布尔类型
+    assert_eq!(
+        form_cell_value(&[FormValue::Checked(true), text("备注")]),
+        ("TRUE, 备注".to_string(), None)
+    );
+}
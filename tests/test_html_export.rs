@@ -0,0 +1,163 @@
+//! HTML 导出测试
+//!
+//! 测试表格分区与合并单元格的还原、文本和样式的转义，以及文档标题和默认样式
+
+use belobog_stellar_grid::{
+    ExportError, ExportFormat, ExportOptions, HtmlCell, HtmlRow, HtmlSection, HtmlSectionKind,
+    HtmlTable, RawCell, SpanMode, build_html_bytes, build_table_data,
+};
+
+fn html_options() -> ExportOptions {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Html);
+    options
+}
+
+fn render(table: &HtmlTable, options: &ExportOptions) -> String {
+    String::from_utf8(build_html_bytes(table, options).unwrap()).unwrap()
+}
+
+fn cell(text: &str) -> HtmlCell {
+    HtmlCell {
+        header: false,
+        text: text.to_string(),
+        colspan: 1,
+        rowspan: 1,
+        style: None,
+    }
+}
+
+#[test]
+fn test_from_table_data_restores_header_and_spans() {
+    let rows = vec![
+        vec![
+            RawCell::with_span("地区", 1, 2),
+            RawCell::with_span("销量", 2, 1),
+        ],
+        vec![RawCell::new("Q1"), RawCell::new("Q2")],
+        vec![RawCell::new("华东"), RawCell::new("1"), RawCell::new("2")],
+    ];
+    let mut table_data = build_table_data(rows, SpanMode::Blank);
+    table_data.header_rows = 2;
+
+    let html = render(&HtmlTable::from_table_data(&table_data), &html_options());
+    assert!(html.contains(
+        "<thead>\n<tr><th rowspan=\"2\">地区</th><th colspan=\"2\">销量</th></tr>\n<tr><th>Q1</th><th>Q2</th></tr>\n</thead>"
+    ));
+    assert!(html.contains("<tbody>\n<tr><td>华东</td><td>1</td><td>2</td></tr>\n</tbody>"));
+}
+
+#[test]
+fn test_sections_and_caption() {
+    let table = HtmlTable {
+        caption: Some("月度报表".to_string()),
+        style: None,
+        sections: vec![
+            HtmlSection {
+                kind: HtmlSectionKind::Body,
+                rows: vec![HtmlRow {
+                    style: None,
+                    cells: vec![cell("a")],
+                }],
+            },
+            HtmlSection {
+                kind: HtmlSectionKind::Foot,
+                rows: vec![HtmlRow {
+                    style: None,
+                    cells: vec![cell("合计")],
+                }],
+            },
+        ],
+    };
+
+    let html = render(&table, &html_options());
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<meta charset=\"utf-8\">"));
+    assert!(html.contains("<title>月度报表</title>"));
+    assert!(html.contains("<caption>月度报表</caption>"));
+    assert!(html.contains("<tfoot>\n<tr><td>合计</td></tr>\n</tfoot>"));
+}
+
+#[test]
+fn test_text_and_styles_are_escaped() {
+    let mut styled = cell("<script>alert(1)</script>\n第二行");
+    styled.style = Some("font-family:\"Noto Sans\";color:red;".to_string());
+    let table = HtmlTable {
+        caption: None,
+        style: None,
+        sections: vec![HtmlSection {
+            kind: HtmlSectionKind::Body,
+            rows: vec![HtmlRow {
+                style: None,
+                cells: vec![styled],
+            }],
+        }],
+    };
+
+    let html = render(&table, &html_options());
+    assert!(!html.contains("<script>"));
+    assert!(html.contains(
+        "<td style=\"font-family:&quot;Noto Sans&quot;;color:red;\">&lt;script&gt;alert(1)&lt;/script&gt;<br>第二行</td>"
+    ));
+}
+
+#[test]
+fn test_default_style_only_without_inline_styles() {
+    let table = HtmlTable {
+        caption: None,
+        style: None,
+        sections: vec![HtmlSection {
+            kind: HtmlSectionKind::Body,
+            rows: vec![HtmlRow {
+                style: None,
+                cells: vec![cell("a")],
+            }],
+        }],
+    };
+
+    let mut options = html_options();
+    options.set_filename(Some("快照.html".to_string()));
+    let html = render(&table, &options);
+    assert!(html.contains("<style>"));
+    assert!(html.contains("<title>快照</title>"));
+
+    options.set_inline_styles(true);
+    assert!(!render(&table, &options).contains("<style>"));
+}
+
+#[test]
+fn test_empty_table() {
+    let error = build_html_bytes(&HtmlTable::default(), &html_options()).unwrap_err();
+    assert_eq!(error, ExportError::EmptyTable);
+}
+
+#[test]
+fn test_html_snapshot_rejects_table_options() {
+    let mut options = html_options();
+    assert_eq!(options.check_html_snapshot(), Ok(()));
+
+    options.set_header_rows(Some(1));
+    let error = options.check_html_snapshot().unwrap_err();
+    assert_eq!(error.code(), "INVALID_ARGUMENT");
+    assert!(error.to_string().contains("header_rows"), "{}", error);
+
+    let mut options = html_options();
+    options.set_include_header(false);
+    assert!(
+        options
+            .check_html_snapshot()
+            .unwrap_err()
+            .to_string()
+            .contains("include_header")
+    );
+
+    let mut options = html_options();
+    options.set_columns(Some(vec![0]));
+    assert!(
+        options
+            .check_html_snapshot()
+            .unwrap_err()
+            .to_string()
+            .contains("columns")
+    );
+}
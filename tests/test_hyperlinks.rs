@@ -0,0 +1,149 @@
+//! 超链接测试
+//!
+//! 测试链接协议过滤、CSV 中带链接单元格的文本、链接在表格变换中的保留，以及 XLSX 中的超链接
+
+mod common;
+
+use belobog_stellar_grid::{
+    ExportFormat, ExportOptions, LinkFormat, RawCell, SpanMode, TableData, build_table_data,
+    build_xlsx_bytes, is_exportable_url, link_text,
+};
+use common::{read_entry, table_from_cells};
+
+const DETAIL_URL: &str = "https://example.com/records/42";
+
+fn linked_table(url: &str) -> TableData {
+    table_from_cells(
+        vec![
+            vec![RawCell::new("记录"), RawCell::new("金额")],
+            vec![RawCell::new("#42").with_link(url), RawCell::new("12.5")],
+        ],
+        1,
+    )
+}
+
+fn xlsx_options() -> ExportOptions {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Xlsx);
+    options
+}
+
+#[test]
+fn test_exportable_url_schemes() {
+    assert!(is_exportable_url("https://example.com/a"));
+    assert!(is_exportable_url("HTTP://example.com"));
+    assert!(is_exportable_url("ftp://files.example.com/report.pdf"));
+    assert!(is_exportable_url("mailto:admin@example.com"));
+
+    assert!(!is_exportable_url("javascript:void(0)"));
+    assert!(!is_exportable_url("data:text/html,hello"));
+    assert!(!is_exportable_url("file:///etc/passwd"));
+    assert!(!is_exportable_url(""));
+}
+
+#[test]
+fn test_link_text_formats() {
+    assert_eq!(link_text("#42", DETAIL_URL, LinkFormat::Text), "#42");
+    assert_eq!(link_text("#42", DETAIL_URL, LinkFormat::Url), DETAIL_URL);
+    assert_eq!(
+        link_text("#42", DETAIL_URL, LinkFormat::Both),
+        "#42 (https://example.com/records/42)"
+    );
+    // 文本为空或与地址相同时不重复输出
+    assert_eq!(link_text(" ", DETAIL_URL, LinkFormat::Both), DETAIL_URL);
+    assert_eq!(
+        link_text(DETAIL_URL, DETAIL_URL, LinkFormat::Both),
+        DETAIL_URL
+    );
+}
+
+#[test]
+fn test_links_follow_spans_and_transforms() {
+    let mut data = build_table_data(
+        vec![
+            vec![RawCell::new("记录"), RawCell::new("备注")],
+            vec![
+                RawCell::with_span("#42", 1, 2).with_link(DETAIL_URL),
+                RawCell::new("a"),
+            ],
+            vec![RawCell::new("b")],
+        ],
+        SpanMode::Repeat,
+    );
+    assert_eq!(
+        data.links.get(&(2, 0)).map(String::as_str),
+        Some(DETAIL_URL)
+    );
+
+    data.header_rows = 1;
+    data.remove_header_rows();
+    data.select_columns(&[1, 0]).unwrap();
+    assert_eq!(data.links.len(), 2);
+    assert_eq!(
+        data.links.get(&(0, 1)).map(String::as_str),
+        Some(DETAIL_URL)
+    );
+    assert_eq!(
+        data.links.get(&(1, 1)).map(String::as_str),
+        Some(DETAIL_URL)
+    );
+}
+
+#[test]
+fn test_xlsx_writes_hyperlink() {
+    let bytes = build_xlsx_bytes(&linked_table(DETAIL_URL), &xlsx_options()).unwrap();
+
+    let sheet = read_entry(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(r#"<hyperlink ref="A2""#));
+    let rels = read_entry(&bytes, "xl/worksheets/_rels/sheet1.xml.rels");
+    assert!(rels.contains(DETAIL_URL));
+    let strings = read_entry(&bytes, "xl/sharedStrings.xml");
+    assert!(strings.contains("#42"));
+}
+
+#[test]
+fn test_xlsx_merged_hyperlink() {
+    let data = build_table_data(
+        vec![
+            vec![RawCell::with_span("详情", 2, 1).with_link(DETAIL_URL)],
+            vec![RawCell::new("1"), RawCell::new("2")],
+        ],
+        SpanMode::Blank,
+    );
+
+    let bytes = build_xlsx_bytes(&data, &xlsx_options()).unwrap();
+    let sheet = read_entry(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(r#"<mergeCell ref="A1:B1"/>"#));
+    assert!(sheet.contains(r#"<hyperlink ref="A1""#));
+}
+
+#[test]
+fn test_xlsx_overlong_url_falls_back_to_text() {
+    // Excel 限制链接地址约 2080 个字符，超出时写入普通单元格而不是导出失败
+    let url = format!("https://example.com/?q={}", "x".repeat(3000));
+    let bytes = build_xlsx_bytes(&linked_table(&url), &xlsx_options()).unwrap();
+
+    let sheet = read_entry(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(!sheet.contains("<hyperlink"));
+    let strings = read_entry(&bytes, "xl/sharedStrings.xml");
+    assert!(strings.contains("#42"));
+}
+
+#[test]
+fn test_xlsx_hyperlinks_limited_per_sheet() {
+    // Excel 每个工作表最多 65,530 个超链接，之后的链接单元格（包括合并单元格）写为普通文本
+    const LIMIT: usize = 65_530;
+    let mut rows: Vec<Vec<RawCell>> = (0..LIMIT + 1)
+        .map(|i| vec![RawCell::new(i.to_string()).with_link(DETAIL_URL)])
+        .collect();
+    rows.push(vec![RawCell::with_span("末尾", 2, 1).with_link(DETAIL_URL)]);
+    let data = build_table_data(rows, SpanMode::Blank);
+
+    let bytes = build_xlsx_bytes(&data, &xlsx_options()).unwrap();
+    let sheet = read_entry(&bytes, "xl/worksheets/sheet1.xml");
+    assert_eq!(sheet.matches("<hyperlink ").count(), LIMIT);
+    assert!(sheet.contains(&format!(r#"<hyperlink ref="A{}""#, LIMIT)));
+    assert!(!sheet.contains(&format!(r#"<hyperlink ref="A{}""#, LIMIT + 1)));
+    assert!(sheet.contains(&format!(r#"<mergeCell ref="A{0}:B{0}"/>"#, LIMIT + 2)));
+    assert!(!sheet.contains(&format!(r#"<hyperlink ref="A{}""#, LIMIT + 2)));
+}
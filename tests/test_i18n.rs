@@ -0,0 +1,80 @@
+//! 国际化测试
+//!
+//! 测试消息目录的多语言输出、语言标签解析以及全局语言设置
+
+use belobog_stellar_grid::{
+    ExportError, Locale, Message, get_locale, set_locale, set_locale_from_tag, validate_filename,
+    validate_sheet_names,
+};
+
+#[test]
+fn test_error_message_in_each_locale() {
+    let error = ExportError::TableNotFound {
+        table_id: "sales".to_string(),
+    };
+
+    assert_eq!(error.message(Locale::Zh), "找不到 ID 为 'sales' 的表格元素");
+    assert_eq!(
+        error.message(Locale::En),
+        "Table element with ID 'sales' not found"
+    );
+    assert!(error.message(Locale::De).contains("'sales'"));
+    assert!(error.message(Locale::Ja).contains("'sales'"));
+}
+
+#[test]
+fn test_validation_reasons_are_localized() {
+    let error = validate_filename("a/b.csv").unwrap_err();
+    assert_eq!(
+        error.message(Locale::En),
+        "Invalid filename: Filename must not contain path separators"
+    );
+
+    let error = validate_sheet_names(&["Data", "data"]).unwrap_err();
+    assert!(error.message(Locale::En).contains("more than once"));
+    assert!(error.message(Locale::Zh).contains("重复"));
+}
+
+#[test]
+fn test_message_positions_are_one_based_in_all_locales() {
+    let message = Message::RowNotArrayOrObject { row: 0 };
+    for locale in [Locale::Zh, Locale::En, Locale::De, Locale::Ja] {
+        assert!(message.localize(locale).contains('1'), "{:?}", locale);
+    }
+}
+
+#[test]
+fn test_locale_from_tag() {
+    assert_eq!(Locale::from_tag("en-US"), Some(Locale::En));
+    assert_eq!(Locale::from_tag("de_AT"), Some(Locale::De));
+    assert_eq!(Locale::from_tag("JA"), Some(Locale::Ja));
+    assert_eq!(Locale::from_tag("zh-Hans-CN"), Some(Locale::Zh));
+    assert_eq!(Locale::from_tag("fr-FR"), None);
+    assert_eq!(Locale::from_tag(""), None);
+}
+
+#[test]
+fn test_default_file_stem_is_localized() {
+    assert_eq!(Locale::En.default_file_stem(), "table_export");
+    assert_eq!(Locale::De.default_file_stem(), "tabellen_export");
+    assert!(validate_filename(&format!("{}.csv", Locale::Ja.default_file_stem())).is_ok());
+}
+
+#[test]
+fn test_global_locale_affects_display() {
+    let error = ExportError::EmptyTable;
+    assert_eq!(get_locale(), Locale::Zh);
+    let chinese = error.to_string();
+
+    set_locale(Locale::En);
+    assert_eq!(error.to_string(), error.message(Locale::En));
+    assert_ne!(error.to_string(), chinese);
+
+    assert!(!set_locale_from_tag("fr"));
+    assert_eq!(get_locale(), Locale::En);
+    assert!(set_locale_from_tag("ja-JP"));
+    assert_eq!(get_locale(), Locale::Ja);
+
+    set_locale(Locale::Zh);
+    assert_eq!(error.to_string(), chinese);
+}
@@ -0,0 +1,43 @@
+//! JavaScript 数据提取测试
+//!
+//! 测试 export_data 从数组的数组和对象数组中提取的表格数据及其表头行。
+//! 需要通过 `wasm-pack test --headless --chrome` 运行
+
+#![cfg(target_arch = "wasm32")]
+
+use belobog_stellar_grid::{ExportFormat, ExportOptions, build_json_bytes, extract_js_data};
+use js_sys::{Array, JSON};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn parse(json: &str) -> JsValue {
+    JSON::parse(json).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_array_rows_use_first_row_as_header() {
+    let data = extract_js_data(&parse(r#"[["姓名", "年龄"], ["张三", 28]]"#), None).unwrap();
+    assert_eq!(data.header_rows, 1);
+    assert_eq!(data.rows, vec![vec!["姓名", "年龄"], vec!["张三", "28"]]);
+
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Json);
+    let json = String::from_utf8(build_json_bytes(&data, &options).unwrap()).unwrap();
+    assert!(json.contains(r#""姓名":"张三""#), "{}", json);
+    assert!(!json.contains("column_1"), "{}", json);
+}
+
+#[wasm_bindgen_test]
+fn test_columns_generate_header_row() {
+    let columns = Array::of2(&"name".into(), &parse(r#"{"key": "age", "title": "年龄"}"#));
+    let data = extract_js_data(&parse(r#"[{"name": "张三", "age": 28}]"#), Some(columns)).unwrap();
+    assert_eq!(data.header_rows, 1);
+    assert_eq!(data.rows, vec![vec!["name", "年龄"], vec!["张三", "28"]]);
+
+    let columns = Array::of1(&"a".into());
+    let data = extract_js_data(&parse(r#"[["1"], ["2"]]"#), Some(columns)).unwrap();
+    assert_eq!(data.header_rows, 1);
+    assert_eq!(data.rows, vec![vec!["a"], vec!["1"], vec!["2"]]);
+}
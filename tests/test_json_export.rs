@@ -0,0 +1,98 @@
+//! JSON 导出测试
+//!
+//! 测试 JSON 与 NDJSON 的两种行布局、表头键的生成以及数字和布尔值的类型推断
+
+mod common;
+
+use belobog_stellar_grid::{
+    CellType, ExportFormat, ExportOptions, JsonLayout, TableData, build_json_bytes,
+};
+use common::table;
+
+fn json_options(format: ExportFormat) -> ExportOptions {
+    let mut options = ExportOptions::new();
+    options.set_format(format);
+    options
+}
+
+fn render(table_data: &TableData, options: &ExportOptions) -> String {
+    String::from_utf8(build_json_bytes(table_data, options).unwrap()).unwrap()
+}
+
+#[test]
+fn test_objects_keyed_by_header() {
+    let data = table(&[&["名称", "数量"], &["苹果", "3"], &["梨", "5"]], 1);
+    let options = json_options(ExportFormat::Json);
+
+    assert_eq!(
+        render(&data, &options),
+        r#"[{"名称":"苹果","数量":"3"},{"名称":"梨","数量":"5"}]"#
+    );
+}
+
+#[test]
+fn test_arrays_layout_includes_header_row() {
+    let data = table(&[&["名称", "数量"], &["苹果", "3"]], 1);
+    let mut options = json_options(ExportFormat::Json);
+    options.set_json_layout(JsonLayout::Arrays);
+
+    assert_eq!(render(&data, &options), r#"[["名称","数量"],["苹果","3"]]"#);
+}
+
+#[test]
+fn test_ndjson_writes_one_record_per_line() {
+    let data = table(&[&["a"], &["1"], &["2"]], 1);
+    let options = json_options(ExportFormat::Ndjson);
+
+    assert_eq!(render(&data, &options), "{\"a\":\"1\"}\n{\"a\":\"2\"}\n");
+}
+
+#[test]
+fn test_missing_and_duplicate_keys() {
+    let data = table(&[&["x", "", "x"], &["1", "2", "3"]], 1);
+    let options = json_options(ExportFormat::Json);
+    assert_eq!(
+        render(&data, &options),
+        r#"[{"x":"1","column_2":"2","x_2":"3"}]"#
+    );
+
+    // 没有表头行时所有行都是数据
+    let data = table(&[&["1", "2"]], 0);
+    assert_eq!(
+        render(&data, &options),
+        r#"[{"column_1":"1","column_2":"2"}]"#
+    );
+}
+
+#[test]
+fn test_type_inference() {
+    let data = table(
+        &[
+            &["编号", "数量", "单价", "折扣", "启用", "日期"],
+            &["00123", "1,200", "-3.5", "12.5%", "TRUE", "2024-01-02"],
+        ],
+        1,
+    );
+    let mut options = json_options(ExportFormat::Json);
+    options.set_json_infer_types(true);
+    options.set_column_types(Some(vec![CellType::Text]));
+
+    assert_eq!(
+        render(&data, &options),
+        r#"[{"编号":"00123","数量":1200,"单价":-3.5,"折扣":0.125,"启用":true,"日期":"2024-01-02"}]"#
+    );
+}
+
+#[test]
+fn test_json_format_metadata() {
+    assert_eq!(ExportFormat::Json.extension(), "json");
+    assert_eq!(ExportFormat::Ndjson.extension(), "ndjson");
+    assert_eq!(
+        ExportFormat::Json.mime_type(),
+        "application/json;charset=utf-8"
+    );
+
+    let options = ExportOptions::new();
+    assert_eq!(options.json_layout(), JsonLayout::Objects);
+    assert!(!options.json_infer_types());
+}
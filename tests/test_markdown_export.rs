@@ -0,0 +1,85 @@
+//! Markdown 导出测试
+//!
+//! 测试 GFM 表格的表头与分隔行、按类型和 CSS 推断的列对齐，以及 `|` 和换行的转义
+
+mod common;
+
+use belobog_stellar_grid::{
+    ColumnAlign, ExportFormat, ExportOptions, TableData, build_markdown_bytes, escape_cell,
+};
+use common::table;
+
+fn render(table_data: &TableData) -> String {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Markdown);
+    String::from_utf8(build_markdown_bytes(table_data, &options).unwrap()).unwrap()
+}
+
+#[test]
+fn test_header_and_separator_rows() {
+    let data = table(&[&["名称", "备注"], &["苹果", "红色"], &["梨", ""]], 1);
+
+    assert_eq!(
+        render(&data),
+        "| 名称 | 备注 |\n| --- | --- |\n| 苹果 | 红色 |\n| 梨 |  |\n"
+    );
+}
+
+#[test]
+fn test_numeric_columns_align_right() {
+    let data = table(
+        &[
+            &["名称", "数量", "占比"],
+            &["苹果", "1,200", "12.5%"],
+            &["梨", "", "-3%"],
+        ],
+        1,
+    );
+
+    let output = render(&data);
+    assert_eq!(output.lines().nth(1), Some("| --- | ---: | ---: |"));
+}
+
+#[test]
+fn test_css_alignment_overrides_inferred() {
+    let mut data = table(&[&["a", "b", "c"], &["x", "1", "y"]], 1);
+    data.alignments = vec![Some(ColumnAlign::Center), None, Some(ColumnAlign::Left)];
+
+    let output = render(&data);
+    assert_eq!(output.lines().nth(1), Some("| :---: | ---: | :--- |"));
+}
+
+#[test]
+fn test_multiple_header_rows_are_joined() {
+    let data = table(
+        &[
+            &["销售", "销售", "备注"],
+            &["Q1", "Q2", ""],
+            &["1", "2", "-"],
+        ],
+        2,
+    );
+
+    let output = render(&data);
+    assert_eq!(
+        output.lines().next(),
+        Some("| 销售 / Q1 | 销售 / Q2 | 备注 |")
+    );
+}
+
+#[test]
+fn test_without_header_rows_uses_empty_header() {
+    let data = table(&[&["a", "b"]], 0);
+
+    assert_eq!(render(&data), "|  |  |\n| --- | --- |\n| a | b |\n");
+}
+
+#[test]
+fn test_escape_pipes_and_newlines() {
+    assert_eq!(escape_cell("a|b"), "a\\|b");
+    assert_eq!(
+        escape_cell("第一行\r\n第二行\n第三行\r第四行"),
+        "第一行<br>第二行<br>第三行<br>第四行"
+    );
+    assert_eq!(escape_cell("  前后空白  "), "前后空白");
+}
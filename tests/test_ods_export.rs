@@ -0,0 +1,123 @@
+//! ODS 导出测试
+//!
+//! 测试 zip 包结构（未压缩的 mimetype 在首位）、带类型的单元格、合并单元格、XML 转义以及空白的保留
+
+mod common;
+
+use belobog_stellar_grid::{CellType, ExportFormat, ExportOptions, RawCell, build_ods_bytes};
+use common::{read_entry, table, table_from_cells};
+use std::io::Cursor;
+use zip::{CompressionMethod, ZipArchive};
+
+fn ods_options() -> ExportOptions {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Ods);
+    options
+}
+
+#[test]
+fn test_package_layout() {
+    let data = table(&[&["a"], &["1"]], 1);
+    let bytes = build_ods_bytes(&data, &ods_options()).unwrap();
+
+    let mut archive = ZipArchive::new(Cursor::new(bytes.as_slice())).unwrap();
+    {
+        let mimetype = archive.by_index(0).unwrap();
+        assert_eq!(mimetype.name(), "mimetype");
+        assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+    }
+    for name in ["content.xml", "styles.xml", "META-INF/manifest.xml"] {
+        assert!(archive.by_name(name).is_ok(), "缺少 {}", name);
+    }
+
+    // mimetype 的内容紧跟在第一个本地文件头之后，不能有扩展字段
+    assert_eq!(
+        &bytes[30..38 + 46],
+        b"mimetypeapplication/vnd.oasis.opendocument.spreadsheet"
+    );
+    assert_eq!(ExportFormat::Ods.extension(), "ods");
+}
+
+#[test]
+fn test_typed_cells() {
+    let data = table(
+        &[
+            &["数量", "占比", "日期", "完成"],
+            &["1,200", "12.5%", "2024-03-05", "true"],
+        ],
+        1,
+    );
+    let content = read_entry(
+        &build_ods_bytes(&data, &ods_options()).unwrap(),
+        "content.xml",
+    );
+
+    assert!(content.contains(r#"office:value-type="string"><text:p>数量</text:p>"#));
+    assert!(
+        content.contains(r#"office:value-type="float" office:value="1200"><text:p>1,200</text:p>"#)
+    );
+    assert!(content.contains(r#"office:value-type="percentage" office:value="0.125""#));
+    assert!(content.contains(r#"office:value-type="date" office:date-value="2024-03-05""#));
+    assert!(content.contains(r#"office:value-type="boolean" office:boolean-value="true""#));
+    assert!(content.contains(r#"<number:percentage-style style:name="N2">"#));
+    assert!(content.contains("<table:table-header-rows>"));
+}
+
+#[test]
+fn test_column_types_override_inference() {
+    let data = table(&[&["编号"], &["00123"]], 1);
+    let mut options = ods_options();
+    options.set_column_types(Some(vec![CellType::Text]));
+
+    let content = read_entry(&build_ods_bytes(&data, &options).unwrap(), "content.xml");
+    assert!(content.contains(r#"office:value-type="string"><text:p>00123</text:p>"#));
+}
+
+#[test]
+fn test_merged_cells() {
+    let rows = vec![
+        vec![RawCell::with_span("合并", 2, 2), RawCell::new("c")],
+        vec![RawCell::new("d")],
+    ];
+    let content = read_entry(
+        &build_ods_bytes(&table_from_cells(rows, 0), &ods_options()).unwrap(),
+        "content.xml",
+    );
+
+    assert!(content.contains(r#"table:number-rows-spanned="2" table:number-columns-spanned="2""#));
+    assert_eq!(content.matches("<table:covered-table-cell/>").count(), 3);
+}
+
+#[test]
+fn test_escape_and_sheet_name() {
+    let data = table(&[&["<a> & \"b\""]], 0);
+    let mut options = ods_options();
+    options.set_sheet_name(Some("R&D".to_string()));
+
+    let content = read_entry(&build_ods_bytes(&data, &options).unwrap(), "content.xml");
+    assert!(content.contains(r#"table:name="R&amp;D""#));
+    assert!(content.contains("<text:p>&lt;a&gt; &amp; &quot;b&quot;</text:p>"));
+}
+
+#[test]
+fn test_whitespace_is_preserved() {
+    let data = table(
+        &[&["a b", "a   b", "  缩进", "末尾 ", "x\ty", "\t 前后\t"]],
+        0,
+    );
+
+    let content = read_entry(
+        &build_ods_bytes(&data, &ods_options()).unwrap(),
+        "content.xml",
+    );
+    for paragraph in [
+        "<text:p>a b</text:p>",
+        r#"<text:p>a<text:s text:c="3"/>b</text:p>"#,
+        r#"<text:p><text:s text:c="2"/>缩进</text:p>"#,
+        "<text:p>末尾<text:s/></text:p>",
+        "<text:p>x<text:tab/>y</text:p>",
+        "<text:p><text:tab/><text:s/>前后<text:tab/></text:p>",
+    ] {
+        assert!(content.contains(paragraph), "{}", paragraph);
+    }
+}
@@ -0,0 +1,169 @@
+//! 公式注入防护测试
+//!
+//! 测试可疑单元格的识别、三种防护方式，以及 CSV、Excel 与 ODS 的默认行为
+
+mod common;
+
+use belobog_stellar_grid::{
+    ExportError, ExportFormat, ExportOptions, FormulaSanitization, RawCell, SpanMode,
+    build_csv_bytes, build_table_data, build_xlsx_bytes, is_formula_like, sanitize_row,
+};
+use common::read_entry;
+
+fn row(cells: &[&str]) -> Vec<String> {
+    cells.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_formula_like_detection() {
+    for text in [
+        "=1+1",
+        "+cmd|' /C calc'!A0",
+        "-2+3",
+        "@SUM(A1)",
+        "\t=1",
+        "\r=1",
+        "- 5",
+        "-inf",
+        "+NaN",
+    ] {
+        assert!(is_formula_like(text), "{:?}", text);
+    }
+
+    for text in ["普通文本", "a=b", "", "1-2"] {
+        assert!(!is_formula_like(text), "{:?}", text);
+    }
+}
+
+#[test]
+fn test_numbers_are_allowlisted() {
+    for text in [
+        "-12",
+        "-12.5",
+        "+3",
+        "-1,234.56",
+        "-5%",
+        "+0.5",
+        "-2e5",
+        "+1.5E-3",
+        "-",
+    ] {
+        assert!(!is_formula_like(text), "{:?}", text);
+    }
+}
+
+#[test]
+fn test_sanitize_modes() {
+    let original = row(&["=HYPERLINK(\"x\")", "-5", "@@cmd", "ok"]);
+
+    let mut cells = original.clone();
+    sanitize_row(&mut cells, 0, FormulaSanitization::Off).unwrap();
+    assert_eq!(cells, original);
+
+    let mut cells = original.clone();
+    sanitize_row(&mut cells, 0, FormulaSanitization::Prefix).unwrap();
+    assert_eq!(cells, row(&["'=HYPERLINK(\"x\")", "-5", "'@@cmd", "ok"]));
+
+    let mut cells = original.clone();
+    sanitize_row(&mut cells, 0, FormulaSanitization::Strip).unwrap();
+    assert_eq!(cells, row(&["HYPERLINK(\"x\")", "-5", "cmd", "ok"]));
+
+    let mut cells = row(&["ok", "-5", "=1"]);
+    let err = sanitize_row(&mut cells, 4, FormulaSanitization::Reject).unwrap_err();
+    assert_eq!(err.code(), "FORMULA_INJECTION");
+    assert_eq!(err, ExportError::FormulaInjection { row: 4, column: 2 });
+}
+
+#[test]
+fn test_strip_keeps_number_sign() {
+    // 去除后会把负数变为正数的单元格改为添加前缀
+    let mut cells = row(&["- 5", "+-12.5", "-\t3", "--cmd", "=-5", "+ 5"]);
+    sanitize_row(&mut cells, 0, FormulaSanitization::Strip).unwrap();
+    assert_eq!(
+        cells,
+        row(&["'- 5", "'+-12.5", "'-\t3", "cmd", "'=-5", " 5"])
+    );
+}
+
+#[test]
+fn test_formula_sanitization_option() {
+    let mut options = ExportOptions::new();
+    assert_eq!(options.formula_sanitization(), None);
+
+    options.set_format(ExportFormat::Xlsx);
+    options.set_formula_sanitization(Some(FormulaSanitization::Reject));
+    assert_eq!(
+        options.formula_sanitization(),
+        Some(FormulaSanitization::Reject)
+    );
+}
+
+#[test]
+fn test_prefix_for_spreadsheets() {
+    // Excel 的 Prefix 由单元格格式实现，不修改文本；ODS 不支持 Prefix
+    let mut options = ExportOptions::new();
+    assert_eq!(options.resolved_sanitization(), FormulaSanitization::Prefix);
+
+    options.set_format(ExportFormat::Xlsx);
+    assert_eq!(options.resolved_sanitization(), FormulaSanitization::Off);
+    options.set_formula_sanitization(Some(FormulaSanitization::Prefix));
+    assert_eq!(options.resolved_sanitization(), FormulaSanitization::Prefix);
+    assert_eq!(options.text_sanitization(), Ok(FormulaSanitization::Off));
+    options.set_formula_sanitization(Some(FormulaSanitization::Reject));
+    assert_eq!(options.text_sanitization(), Ok(FormulaSanitization::Reject));
+
+    options.set_format(ExportFormat::Ods);
+    options.set_formula_sanitization(Some(FormulaSanitization::Strip));
+    assert_eq!(options.text_sanitization(), Ok(FormulaSanitization::Strip));
+    options.set_formula_sanitization(Some(FormulaSanitization::Prefix));
+    assert_eq!(
+        options.text_sanitization().unwrap_err().code(),
+        "INVALID_ARGUMENT"
+    );
+    options.set_formula_sanitization(None);
+    assert_eq!(options.text_sanitization(), Ok(FormulaSanitization::Off));
+
+    options.set_format(ExportFormat::Json);
+    options.set_formula_sanitization(Some(FormulaSanitization::Prefix));
+    assert_eq!(options.text_sanitization(), Ok(FormulaSanitization::Prefix));
+}
+
+#[test]
+fn test_xlsx_prefix_sets_quote_prefix() {
+    let table_data = build_table_data(
+        vec![vec![RawCell::new("=1+1"), RawCell::new("ok")]],
+        SpanMode::Blank,
+    );
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Xlsx);
+    assert!(
+        !read_entry(
+            &build_xlsx_bytes(&table_data, &options).unwrap(),
+            "xl/styles.xml"
+        )
+        .contains("quotePrefix")
+    );
+
+    options.set_formula_sanitization(Some(FormulaSanitization::Prefix));
+    let bytes = build_xlsx_bytes(&table_data, &options).unwrap();
+    assert!(read_entry(&bytes, "xl/styles.xml").contains(r#"quotePrefix="1""#));
+    let sheet = read_entry(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(r#"<c r="A1" s="1" t="s">"#), "{}", sheet);
+    assert!(sheet.contains(r#"<c r="B1" t="s">"#), "{}", sheet);
+    let strings = read_entry(&bytes, "xl/sharedStrings.xml");
+    assert!(strings.contains("<t>=1+1</t>") && !strings.contains("'"));
+}
+
+#[test]
+fn test_csv_build_with_prefixed_cells() {
+    let mut table_data = build_table_data(
+        vec![vec![RawCell::new("=1+1"), RawCell::new("-3")]],
+        SpanMode::Blank,
+    );
+    for (index, row) in table_data.rows.iter_mut().enumerate() {
+        sanitize_row(row, index, FormulaSanitization::Prefix).unwrap();
+    }
+
+    let csv = build_csv_bytes(&table_data, &ExportOptions::new()).unwrap();
+    assert_eq!(String::from_utf8(csv).unwrap(), "'=1+1,-3\r\n");
+}
@@ -0,0 +1,50 @@
+//! 工作表名称验证测试
+//!
+//! 测试多工作表导出时对 Excel 工作表名称规则的验证
+
+use belobog_stellar_grid::{validate_sheet_name, validate_sheet_names};
+
+#[test]
+fn test_valid_sheet_names() {
+    assert!(validate_sheet_name("Sheet1").is_ok());
+    assert!(validate_sheet_name("汇总").is_ok());
+    assert!(validate_sheet_name("2024 Q1 (明细)").is_ok());
+    assert!(validate_sheet_name(&"表".repeat(31)).is_ok());
+}
+
+#[test]
+fn test_sheet_name_empty_or_too_long() {
+    assert!(validate_sheet_name("").is_err());
+
+    let err = validate_sheet_name(&"a".repeat(32)).unwrap_err();
+    assert_eq!(err.code(), "INVALID_SHEET_NAME");
+    assert!(err.to_string().contains("31"));
+    assert!(validate_sheet_name(&"表".repeat(32)).is_err());
+}
+
+#[test]
+fn test_sheet_name_forbidden_chars() {
+    for name in ["a[b", "a]b", "a:b", "a*b", "a?b", "a/b", "a\\b"] {
+        assert!(validate_sheet_name(name).is_err(), "{} 应当被拒绝", name);
+    }
+}
+
+#[test]
+fn test_sheet_name_apostrophe() {
+    assert!(validate_sheet_name("'汇总").is_err());
+    assert!(validate_sheet_name("汇总'").is_err());
+    assert!(validate_sheet_name("O'Brien").is_ok());
+}
+
+#[test]
+fn test_sheet_names_unique_case_insensitive() {
+    assert!(validate_sheet_names(&["汇总", "明细", "异常"]).is_ok());
+
+    let err = validate_sheet_names(&["Summary", "summary"]).unwrap_err();
+    assert!(err.to_string().contains("重复"));
+}
+
+#[test]
+fn test_sheet_names_reports_invalid_entry() {
+    assert!(validate_sheet_names(&["汇总", "a/b"]).is_err());
+}
@@ -0,0 +1,302 @@
+//! 合并单元格展开测试
+//!
+//! 测试 colspan/rowspan 展开为矩形矩阵的逻辑
+
+use belobog_stellar_grid::{MergeRange, RawCell, SpanGrid, SpanMode, build_table_data};
+
+fn texts(row: &[&str]) -> Vec<String> {
+    row.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_span_grid_without_spans() {
+    let data = build_table_data(
+        vec![
+            vec![RawCell::new("A"), RawCell::new("B")],
+            vec![RawCell::new("1"), RawCell::new("2")],
+        ],
+        SpanMode::Blank,
+    );
+
+    assert_eq!(data.rows, vec![texts(&["A", "B"]), texts(&["1", "2"])]);
+    assert!(data.merges.is_empty());
+}
+
+#[test]
+fn test_span_grid_two_level_header_blank() {
+    // | 姓名(rowspan=2) | 成绩(colspan=2) |
+    // |                 | 语文 | 数学      |
+    let data = build_table_data(
+        vec![
+            vec![
+                RawCell::with_span("姓名", 1, 2),
+                RawCell::with_span("成绩", 2, 1),
+            ],
+            vec![RawCell::new("语文"), RawCell::new("数学")],
+            vec![RawCell::new("张三"), RawCell::new("90"), RawCell::new("85")],
+        ],
+        SpanMode::Blank,
+    );
+
+    assert_eq!(
+        data.rows,
+        vec![
+            texts(&["姓名", "成绩", ""]),
+            texts(&["", "语文", "数学"]),
+            texts(&["张三", "90", "85"]),
+        ]
+    );
+    assert_eq!(
+        data.merges,
+        vec![
+            MergeRange {
+                first_row: 0,
+                first_col: 0,
+                last_row: 1,
+                last_col: 0
+            },
+            MergeRange {
+                first_row: 0,
+                first_col: 1,
+                last_row: 0,
+                last_col: 2
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_span_grid_two_level_header_repeat() {
+    let data = build_table_data(
+        vec![
+            vec![
+                RawCell::with_span("姓名", 1, 2),
+                RawCell::with_span("成绩", 2, 1),
+            ],
+            vec![RawCell::new("语文"), RawCell::new("数学")],
+        ],
+        SpanMode::Repeat,
+    );
+
+    assert_eq!(
+        data.rows,
+        vec![
+            texts(&["姓名", "成绩", "成绩"]),
+            texts(&["姓名", "语文", "数学"])
+        ]
+    );
+}
+
+#[test]
+fn test_span_grid_rowspan_in_middle_and_trailing() {
+    // 中间列和末尾列的 rowspan 都应被正确跳过
+    let data = build_table_data(
+        vec![
+            vec![
+                RawCell::new("a"),
+                RawCell::with_span("b", 1, 2),
+                RawCell::new("c"),
+                RawCell::with_span("d", 1, 2),
+            ],
+            vec![RawCell::new("e"), RawCell::new("f")],
+        ],
+        SpanMode::Repeat,
+    );
+
+    assert_eq!(
+        data.rows,
+        vec![texts(&["a", "b", "c", "d"]), texts(&["e", "b", "f", "d"])]
+    );
+}
+
+#[test]
+fn test_span_grid_pads_ragged_rows() {
+    let data = build_table_data(
+        vec![
+            vec![RawCell::new("a"), RawCell::new("b"), RawCell::new("c")],
+            vec![RawCell::new("d")],
+        ],
+        SpanMode::Blank,
+    );
+
+    assert_eq!(
+        data.rows,
+        vec![texts(&["a", "b", "c"]), texts(&["d", "", ""])]
+    );
+}
+
+#[test]
+fn test_span_grid_clamps_rowspan_past_end() {
+    let data = build_table_data(
+        vec![
+            vec![RawCell::with_span("a", 1, 5), RawCell::new("b")],
+            vec![RawCell::new("c")],
+        ],
+        SpanMode::Blank,
+    );
+
+    assert_eq!(data.rows, vec![texts(&["a", "b"]), texts(&["", "c"])]);
+    assert_eq!(data.merges[0].last_row, 1);
+}
+
+#[test]
+fn test_span_grid_zero_span_treated_as_one() {
+    let cell = RawCell::with_span("x", 0, 0);
+    assert_eq!(cell.colspan, 1);
+    assert_eq!(cell.rowspan, 1);
+}
+
+#[test]
+fn test_span_grid_streaming_across_batches() {
+    // 分批导出时，rowspan 状态需要跨越多次 push_row 调用
+    let mut grid = SpanGrid::new(SpanMode::Repeat);
+    let first = grid.push_row(vec![RawCell::with_span("组", 1, 3), RawCell::new("1")]);
+    let second = grid.push_row(vec![RawCell::new("2")]);
+    let third = grid.push_row(vec![RawCell::new("3")]);
+
+    assert_eq!(first, texts(&["组", "1"]));
+    assert_eq!(second, texts(&["组", "2"]));
+    assert_eq!(third, texts(&["组", "3"]));
+    assert_eq!(grid.row_count(), 3);
+    assert_eq!(grid.finish().len(), 1);
+}
+
+#[test]
+fn test_span_grid_into_table_data_streaming() {
+    // 分批导出逐行调用 push_row，最后组装为与 build_table_data 相同的结果
+    let raw_rows = vec![
+        vec![RawCell::with_span("类别", 1, 2), RawCell::new("A")],
+        vec![RawCell::new("B"), RawCell::new("额外")],
+    ];
+    let expected = build_table_data(raw_rows.clone(), SpanMode::Blank);
+
+    let mut grid = SpanGrid::new(SpanMode::Blank);
+    let rows: Vec<Vec<String>> = raw_rows.into_iter().map(|row| grid.push_row(row)).collect();
+    let data = grid.into_table_data(rows);
+
+    assert_eq!(data.rows, expected.rows);
+    assert_eq!(data.merges, expected.merges);
+    assert_eq!(
+        data.rows,
+        vec![texts(&["类别", "A", ""]), texts(&["", "B", "额外"])]
+    );
+}
+
+#[test]
+fn test_span_grid_colspan_stops_at_pending_rowspan() {
+    // | A | B(rowspan=2) |
+    // | C(colspan=2)    |  <- 第 2 列仍被 B 占用，C 的 colspan 在此截断
+    // | D | E           |
+    let data = build_table_data(
+        vec![
+            vec![RawCell::new("A"), RawCell::with_span("B", 1, 2)],
+            vec![RawCell::with_span("C", 2, 1)],
+            vec![RawCell::new("D"), RawCell::new("E")],
+        ],
+        SpanMode::Blank,
+    );
+
+    assert_eq!(
+        data.rows,
+        vec![texts(&["A", "B"]), texts(&["C", ""]), texts(&["D", "E"])]
+    );
+    assert_eq!(
+        data.merges,
+        vec![MergeRange {
+            first_row: 0,
+            first_col: 1,
+            last_row: 1,
+            last_col: 1
+        }]
+    );
+}
+
+#[test]
+fn test_span_grid_colspan_truncated_keeps_merge_before_pending() {
+    // colspan=3 从第 1 列开始，第 3 列被 rowspan 占用时只合并前两列
+    let data = build_table_data(
+        vec![
+            vec![
+                RawCell::new("A"),
+                RawCell::new("B"),
+                RawCell::with_span("C", 1, 2),
+            ],
+            vec![RawCell::with_span("D", 3, 1)],
+        ],
+        SpanMode::Repeat,
+    );
+
+    assert_eq!(data.rows[1], texts(&["D", "D", "C"]));
+    assert!(data.merges.contains(&MergeRange {
+        first_row: 1,
+        first_col: 0,
+        last_row: 1,
+        last_col: 1
+    }));
+    assert_eq!(data.merges.len(), 2);
+}
+
+#[test]
+fn test_span_grid_rowspan_stops_at_section_boundary() {
+    // thead 中的 rowspan=3 不会延伸到 tbody
+    let mut grid = SpanGrid::new(SpanMode::Blank);
+    let mut rows = vec![
+        grid.push_row(vec![RawCell::with_span("姓名", 1, 3), RawCell::new("成绩")]),
+        grid.push_row(vec![RawCell::new("语文")]),
+    ];
+    grid.end_section();
+    rows.push(grid.push_row(vec![RawCell::new("张三"), RawCell::new("90")]));
+    let data = grid.into_table_data(rows);
+
+    assert_eq!(
+        data.rows,
+        vec![
+            texts(&["姓名", "成绩"]),
+            texts(&["", "语文"]),
+            texts(&["张三", "90"])
+        ]
+    );
+    assert_eq!(
+        data.merges,
+        vec![MergeRange {
+            first_row: 0,
+            first_col: 0,
+            last_row: 1,
+            last_col: 0
+        }]
+    );
+}
+
+#[test]
+fn test_span_grid_end_section_drops_single_row_merges() {
+    // 分区只有一行时，被截断的 rowspan 不再需要合并
+    let mut grid = SpanGrid::new(SpanMode::Blank);
+    let first = grid.push_row(vec![RawCell::with_span("合计", 1, 2), RawCell::new("1")]);
+    grid.end_section();
+    let second = grid.push_row(vec![RawCell::new("a"), RawCell::new("b")]);
+
+    assert_eq!(first, texts(&["合计", "1"]));
+    assert_eq!(second, texts(&["a", "b"]));
+    assert!(grid.finish().is_empty());
+}
+
+#[test]
+fn test_span_grid_last_row_cells() {
+    // | A(rowspan=2) | B(colspan=2) |
+    // |              | C | D        |  <- C、D 被 A 推到第 2、3 列
+    let mut grid = SpanGrid::new(SpanMode::Blank);
+    grid.push_row(vec![
+        RawCell::with_span("A", 1, 2),
+        RawCell::with_span("B", 2, 1),
+    ]);
+    assert_eq!(grid.last_row_cells(), &[(0, 1), (1, 2)]);
+
+    grid.push_row(vec![RawCell::new("C"), RawCell::new("D")]);
+    assert_eq!(grid.last_row_cells(), &[(1, 1), (2, 1)]);
+
+    // colspan 被上方 rowspan 截断时报告实际的列数
+    let mut grid = SpanGrid::new(SpanMode::Blank);
+    grid.push_row(vec![RawCell::new("A"), RawCell::with_span("B", 1, 2)]);
+    grid.push_row(vec![RawCell::with_span("C", 2, 1)]);
+    assert_eq!(grid.last_row_cells(), &[(0, 1)]);
+}
@@ -0,0 +1,132 @@
+//! 单元格类型推断测试
+//!
+//! 测试 Excel 导出时的数字、百分比、布尔值和日期识别
+
+use belobog_stellar_grid::{CellType, CellValue, infer_cell_value, resolve_cell_value};
+
+#[test]
+fn test_infer_integers_and_decimals() {
+    assert_eq!(
+        infer_cell_value("42"),
+        CellValue::Number {
+            value: 42.0,
+            decimals: 0,
+            grouped: false
+        }
+    );
+    assert_eq!(
+        infer_cell_value("-3.50"),
+        CellValue::Number {
+            value: -3.5,
+            decimals: 2,
+            grouped: false
+        }
+    );
+    assert_eq!(
+        infer_cell_value(" 1,234,567.8 "),
+        CellValue::Number {
+            value: 1234567.8,
+            decimals: 1,
+            grouped: true
+        }
+    );
+    assert!(matches!(
+        infer_cell_value("0"),
+        CellValue::Number { value: 0.0, .. }
+    ));
+    assert!(matches!(infer_cell_value("0.5"), CellValue::Number { .. }));
+}
+
+#[test]
+fn test_infer_keeps_identifiers_as_text() {
+    // 前导零、超长数字和格式不正确的千位分隔符都应保留为文本
+    assert_eq!(infer_cell_value("00123"), CellValue::Text);
+    assert_eq!(infer_cell_value("6222021234567890123"), CellValue::Text);
+    assert_eq!(infer_cell_value("1,23"), CellValue::Text);
+    assert_eq!(infer_cell_value("12a"), CellValue::Text);
+    assert_eq!(infer_cell_value("1."), CellValue::Text);
+    assert_eq!(infer_cell_value("NaN"), CellValue::Text);
+    assert_eq!(infer_cell_value("inf"), CellValue::Text);
+    assert_eq!(infer_cell_value(""), CellValue::Text);
+}
+
+#[test]
+fn test_infer_percent_and_boolean() {
+    assert_eq!(
+        infer_cell_value("12.5%"),
+        CellValue::Percent {
+            value: 0.125,
+            decimals: 1
+        }
+    );
+    assert_eq!(infer_cell_value("TRUE"), CellValue::Boolean(true));
+    assert_eq!(infer_cell_value("false"), CellValue::Boolean(false));
+    assert_eq!(infer_cell_value("yes"), CellValue::Text);
+}
+
+#[test]
+fn test_infer_dates() {
+    assert_eq!(
+        infer_cell_value("2024-02-29"),
+        CellValue::Date {
+            year: 2024,
+            month: 2,
+            day: 29
+        }
+    );
+    assert_eq!(
+        infer_cell_value("2024-01-31T08:30:15Z"),
+        CellValue::DateTime {
+            year: 2024,
+            month: 1,
+            day: 31,
+            hour: 8,
+            minute: 30,
+            second: 15.0
+        }
+    );
+    assert!(matches!(
+        infer_cell_value("2024-01-31 08:30"),
+        CellValue::DateTime { second: 0.0, .. }
+    ));
+    assert_eq!(infer_cell_value("2023-02-29"), CellValue::Text);
+    assert_eq!(infer_cell_value("2024-13-01"), CellValue::Text);
+    assert_eq!(infer_cell_value("2024-01-01 25:00"), CellValue::Text);
+}
+
+#[test]
+fn test_resolve_with_column_override() {
+    assert_eq!(resolve_cell_value("00123", CellType::Text), CellValue::Text);
+    assert_eq!(resolve_cell_value("123", CellType::Text), CellValue::Text);
+    assert!(matches!(
+        resolve_cell_value("00123", CellType::Number),
+        CellValue::Number { value: 123.0, .. }
+    ));
+    assert_eq!(resolve_cell_value("abc", CellType::Number), CellValue::Text);
+    assert_eq!(
+        resolve_cell_value("2024-01-01", CellType::Boolean),
+        CellValue::Text
+    );
+    assert_eq!(
+        resolve_cell_value("42", CellType::Auto),
+        infer_cell_value("42")
+    );
+}
+
+#[test]
+fn test_num_format() {
+    assert_eq!(infer_cell_value("42").num_format(), None);
+    assert_eq!(
+        infer_cell_value("1,234.50").num_format().as_deref(),
+        Some("#,##0.00")
+    );
+    assert_eq!(
+        infer_cell_value("3.14").num_format().as_deref(),
+        Some("0.00")
+    );
+    assert_eq!(infer_cell_value("50%").num_format().as_deref(), Some("0%"));
+    assert_eq!(
+        infer_cell_value("2024-01-01").num_format().as_deref(),
+        Some("yyyy-mm-dd")
+    );
+}
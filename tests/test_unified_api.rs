@@ -27,3 +27,23 @@ fn test_export_format_equality() {
     assert_eq!(ExportFormat::Csv, ExportFormat::default());
     assert_ne!(ExportFormat::Xlsx, ExportFormat::Csv);
 }
+
+#[test]
+fn test_export_format_extension() {
+    assert_eq!(ExportFormat::Csv.extension(), "csv");
+    assert_eq!(ExportFormat::Xlsx.extension(), "xlsx");
+    assert_eq!(ExportFormat::Tsv.extension(), "tsv");
+}
+
+#[test]
+fn test_export_format_mime_type() {
+    assert_eq!(ExportFormat::Csv.mime_type(), "text/csv;charset=utf-8");
+    assert_eq!(
+        ExportFormat::Xlsx.mime_type(),
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+    );
+    assert_eq!(
+        ExportFormat::Tsv.mime_type(),
+        "text/tab-separated-values;charset=utf-8"
+    );
+}
@@ -0,0 +1,92 @@
+//! Excel 合并单元格测试
+//!
+//! 测试 colspan/rowspan 展开后的合并区域写入 XLSX 工作表的 `<mergeCell>`
+
+mod common;
+
+use belobog_stellar_grid::{
+    ExportFormat, ExportOptions, RawCell, SpanMode, TableData, build_table_data, build_xlsx_bytes,
+};
+
+use common::read_entry;
+
+fn sheet_xml(data: &TableData) -> String {
+    let mut options = ExportOptions::new();
+    options.set_format(ExportFormat::Xlsx);
+    let bytes = build_xlsx_bytes(data, &options).unwrap();
+    read_entry(&bytes, "xl/worksheets/sheet1.xml")
+}
+
+fn two_level_header(mode: SpanMode) -> TableData {
+    // | 姓名(rowspan=2) | 成绩(colspan=2) |
+    // |                 | 语文 | 数学      |
+    build_table_data(
+        vec![
+            vec![
+                RawCell::with_span("姓名", 1, 2),
+                RawCell::with_span("成绩", 2, 1),
+            ],
+            vec![RawCell::new("语文"), RawCell::new("数学")],
+            vec![RawCell::new("张三"), RawCell::new("90"), RawCell::new("85")],
+        ],
+        mode,
+    )
+}
+
+#[test]
+fn test_xlsx_writes_merge_cells() {
+    for mode in [SpanMode::Blank, SpanMode::Repeat] {
+        let sheet = sheet_xml(&two_level_header(mode));
+
+        assert!(sheet.contains(r#"<mergeCells count="2">"#), "{mode:?}");
+        assert!(sheet.contains(r#"<mergeCell ref="A1:A2"/>"#), "{mode:?}");
+        assert!(sheet.contains(r#"<mergeCell ref="B1:C1"/>"#), "{mode:?}");
+    }
+}
+
+#[test]
+fn test_xlsx_without_spans_has_no_merge_cells() {
+    let data = build_table_data(
+        vec![
+            vec![RawCell::new("A"), RawCell::new("B")],
+            vec![RawCell::new("1"), RawCell::new("2")],
+        ],
+        SpanMode::Blank,
+    );
+
+    assert!(!sheet_xml(&data).contains("<mergeCell"));
+}
+
+#[test]
+fn test_xlsx_colspan_over_pending_rowspan_does_not_overlap() {
+    // | A | B(rowspan=2) |
+    // | C(colspan=2)    |  <- colspan 在被 B 占用的列前截断，不会与 B1:B2 重叠
+    let data = build_table_data(
+        vec![
+            vec![RawCell::new("A"), RawCell::with_span("B", 1, 2)],
+            vec![RawCell::with_span("C", 2, 1)],
+            vec![RawCell::new("D"), RawCell::new("E")],
+        ],
+        SpanMode::Blank,
+    );
+
+    let sheet = sheet_xml(&data);
+    assert!(sheet.contains(r#"<mergeCells count="1">"#));
+    assert!(sheet.contains(r#"<mergeCell ref="B1:B2"/>"#));
+}
+
+#[test]
+fn test_xlsx_merges_follow_column_selection() {
+    // 交换列后合并区域仍然连续，跟随移动；被拆开的合并区域不再写入
+    let mut data = two_level_header(SpanMode::Blank);
+    data.select_columns(&[1, 2, 0]).unwrap();
+    let sheet = sheet_xml(&data);
+    assert!(sheet.contains(r#"<mergeCell ref="A1:B1"/>"#));
+    assert!(sheet.contains(r#"<mergeCell ref="C1:C2"/>"#));
+
+    let mut data = two_level_header(SpanMode::Blank);
+    data.select_columns(&[1, 0, 2]).unwrap();
+    let sheet = sheet_xml(&data);
+    assert!(sheet.contains(r#"<mergeCells count="1">"#));
+    assert!(sheet.contains(r#"<mergeCell ref="B1:B2"/>"#));
+}